@@ -1,14 +1,45 @@
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use solana_sdk::{
-    account::Account, commitment_config::CommitmentLevel, hash::Hash, pubkey::Pubkey,
-    transaction::Transaction,
+    account::Account,
+    clock::{Slot, UnixTimestamp},
+    commitment_config::CommitmentLevel,
+    hash::Hash,
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::{Transaction, TransactionError},
+};
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, TransactionStatus,
+    UiConfirmedBlock, UiInnerInstructions, UiInstruction, UiMessage, UiTransaction,
+    UiTransactionEncoding,
 };
-use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
 use std::str::FromStr;
 
 pub const DEFAULT_SERVER_URL: &str = "http://127.0.0.1:3030";
 pub const DEFAULT_RPC_ENDPOINT: &str = "https://api.mainnet-beta.solana.com/";
+pub const DEFAULT_COMPUTE_UNIT_MARGIN_PERCENT: u64 = 10;
+pub const DEFAULT_LARGEST_TOKEN_ACCOUNTS_LIMIT: usize = 20;
+pub const DEFAULT_SIGNATURES_FOR_ADDRESS_LIMIT: usize = 1000;
+/// Candidate sets up to this size are searched exhaustively by default.
+pub const DEFAULT_EXHAUSTIVE_SEARCH_LIMIT: usize = 6;
+/// Largest candidate set searched exhaustively, 40320 orderings.
+pub const MAX_EXHAUSTIVE_SEARCH_CANDIDATES: usize = 8;
+pub const DEFAULT_BEAM_WIDTH: usize = 4;
+pub const MAX_BEAM_WIDTH: usize = 32;
+pub const DEFAULT_MAX_BUNDLE_TRANSACTIONS: usize = 5;
+pub const DEFAULT_MIN_BUNDLE_TIP_LAMPORTS: u64 = 1000;
+/// Jito tip payment accounts on mainnet-beta.
+pub const JITO_TIP_ACCOUNTS: [&str; 8] = [
+    "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5",
+    "HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe",
+    "Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY",
+    "ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49",
+    "DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh",
+    "ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt",
+    "DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL",
+    "3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT",
+];
 
 pub struct ExecutorClient {
     pub url: Url,
@@ -37,6 +68,875 @@ pub struct RpcConfig {
     pub commitment_level: CommitmentLevel,
 }
 
+/// Order in which the pending transactions are executed when a block is sealed.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderingPolicy {
+    /// Submission order.
+    Fifo,
+    /// Highest compute unit price first, ties kept in submission order.
+    PriorityFee,
+    /// Only the listed transactions, in the given order. The others stay pending.
+    Explicit(Vec<Signature>),
+}
+
+impl Default for OrderingPolicy {
+    fn default() -> Self {
+        OrderingPolicy::Fifo
+    }
+}
+
+/// When the working bank is frozen and recorded as a block.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockProduction {
+    /// No blocks are recorded, closing or sealing a block only moves on to the next slot.
+    Disabled,
+    /// Blocks are closed explicitly.
+    Manual,
+    /// Each executed batch closes a block.
+    PerBatch,
+    /// Each blockhash advance closes a block.
+    PerBlockhash,
+}
+
+impl Default for BlockProduction {
+    fn default() -> Self {
+        BlockProduction::Disabled
+    }
+}
+
+/// Compute budget granted to executed transactions instead of the one derived by the bank.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ComputeBudgetOverrides {
+    /// Compute unit limit granted regardless of the transaction's request.
+    pub compute_unit_limit: Option<u32>,
+    /// Units granted per instruction when the transaction doesn't set a limit.
+    pub default_units_per_instruction: Option<u32>,
+    /// Upper bound of the compute unit limit.
+    pub max_compute_unit_limit: Option<u32>,
+    /// Program heap size in bytes, a multiple of 1024 between 32KiB and 256KiB.
+    pub heap_frame_bytes: Option<u32>,
+    /// Ignore the transaction's ComputeBudget instructions, only applying the overrides. Invalid
+    /// or duplicate instructions are still rejected, like a leader does.
+    #[serde(default)]
+    pub ignore_compute_budget_instructions: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ComputeUnitsEstimateRequest {
+    pub transaction: Transaction,
+    #[serde(default = "default_compute_unit_margin_percent")]
+    pub margin_percent: u64,
+}
+
+fn default_compute_unit_margin_percent() -> u64 {
+    DEFAULT_COMPUTE_UNIT_MARGIN_PERCENT
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ComputeUnitsEstimate {
+    pub compute_units_consumed: u64,
+    pub recommended_compute_unit_limit: u32,
+    /// Error the transaction failed with, in which case the consumption may be partial.
+    pub error: Option<TransactionError>,
+}
+
+/// Name and message of a program's custom error code.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ProgramErrorDefinition {
+    pub code: u32,
+    pub name: String,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RegisterProgramErrorsRequest {
+    pub program_id: Pubkey,
+    pub errors: Vec<ProgramErrorDefinition>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RegisterIdlRequest {
+    pub program_id: Pubkey,
+    /// IDL JSON, fetched from the program's on-chain IDL account when omitted.
+    #[serde(default)]
+    pub idl: Option<String>,
+}
+
+/// Instruction error of a failed transaction, attributed to the program which raised it.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DecodedError {
+    pub instruction_index: u8,
+    pub program_id: Pubkey,
+    /// Custom error code, if the program returned one.
+    pub code: Option<u32>,
+    /// Error name, when known.
+    pub name: Option<String>,
+    pub message: String,
+}
+
+/// State override applied to an account for the duration of a single simulation.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum AccountOverride {
+    /// Replace the account entirely.
+    Replace(Account),
+    /// Patch selected fields of the current account, or of an empty system account if none exists.
+    Patch(AccountPatch),
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct AccountPatch {
+    pub lamports: Option<u64>,
+    pub owner: Option<Pubkey>,
+    /// Byte ranges written over the account data, growing it if needed.
+    #[serde(default)]
+    pub data: Vec<DataPatch>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DataPatch {
+    pub offset: usize,
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SimulationRequest {
+    pub batch: Vec<Transaction>,
+    #[serde(default)]
+    pub account_overrides: Vec<(Pubkey, AccountOverride)>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct EncodingConfig {
+    pub encoding: Option<UiTransactionEncoding>,
+    /// Include the account diffs of each transaction in the results.
+    #[serde(default)]
+    pub account_diffs: bool,
+}
+
+/// Changes a transaction made to one of its writable accounts. Missing accounts are
+/// treated as empty ones.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountDiff {
+    pub pubkey: Pubkey,
+    pub pre_lamports: u64,
+    pub post_lamports: u64,
+    /// Previous and new owner, if it changed.
+    pub owner_change: Option<(Pubkey, Pubkey)>,
+    pub pre_data_len: usize,
+    pub post_data_len: usize,
+    pub data_changes: Vec<DataChange>,
+}
+
+/// Contiguous range of account data which changed. When the data is resized, the bytes
+/// past the shorter length are reported as changed.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataChange {
+    pub offset: usize,
+    pub pre: Vec<u8>,
+    pub post: Vec<u8>,
+}
+
+/// Program invocation reconstructed from the execution logs, with the CPIs it made.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Invocation {
+    pub program_id: Pubkey,
+    pub data: Vec<u8>,
+    pub accounts: Vec<Pubkey>,
+    pub stack_height: usize,
+    /// Units consumed by this invocation, including its inner invocations.
+    /// Builtin programs don't report it.
+    pub compute_units_consumed: Option<u64>,
+    /// Log lines emitted by this invocation itself, excluding its inner invocations.
+    pub logs: Vec<String>,
+    pub error: Option<String>,
+    /// Instruction decoded through the program's registered IDL.
+    pub decoded_instruction: Option<DecodedInstruction>,
+    /// Events emitted through `Program data:` logs, decoded through the program's IDL.
+    pub events: Vec<DecodedEvent>,
+    pub inner_invocations: Vec<Invocation>,
+}
+
+/// Borsh value decoded through an IDL type.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum DecodedValue {
+    Bool(bool),
+    Unsigned(u128),
+    Signed(i128),
+    Float(f64),
+    String(String),
+    Bytes(Vec<u8>),
+    Pubkey(Pubkey),
+    Option(Option<Box<DecodedValue>>),
+    Array(Vec<DecodedValue>),
+    Struct(Vec<(String, DecodedValue)>),
+    Tuple(Vec<DecodedValue>),
+    Enum {
+        variant: String,
+        value: Option<Box<DecodedValue>>,
+    },
+}
+
+/// Filter of a program accounts query, with the semantics of the RPC `getProgramAccounts` ones.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AccountFilter {
+    DataSize(u64),
+    Memcmp { offset: usize, bytes: Vec<u8> },
+}
+
+impl AccountFilter {
+    pub fn matches(&self, data: &[u8]) -> bool {
+        match self {
+            AccountFilter::DataSize(size) => data.len() as u64 == *size,
+            AccountFilter::Memcmp { offset, bytes } => data
+                .get(*offset..)
+                .map_or(false, |data| data.starts_with(bytes)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GetProgramAccountsRequest {
+    pub program_id: Pubkey,
+    #[serde(default)]
+    pub filters: Vec<AccountFilter>,
+}
+
+/// Balance of an SPL Token account.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TokenAccountBalance {
+    pub pubkey: Pubkey,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub decimals: u8,
+    pub ui_amount: f64,
+}
+
+impl TokenAccountBalance {
+    pub fn new(pubkey: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64, decimals: u8) -> Self {
+        Self {
+            pubkey,
+            mint,
+            owner,
+            amount,
+            decimals,
+            ui_amount: amount as f64 / 10f64.powi(decimals as i32),
+        }
+    }
+}
+
+/// Token accounts of an owner or a delegate, optionally restricted to a mint.
+#[derive(Serialize, Deserialize)]
+pub struct TokenAccountsRequest {
+    pub pubkey: Pubkey,
+    #[serde(default)]
+    pub mint: Option<Pubkey>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LargestTokenAccountsRequest {
+    pub mint: Pubkey,
+    #[serde(default = "default_largest_token_accounts_limit")]
+    pub limit: usize,
+}
+
+fn default_largest_token_accounts_limit() -> usize {
+    DEFAULT_LARGEST_TOKEN_ACCOUNTS_LIMIT
+}
+
+/// Produced slots from `start_slot` to `end_slot`, both included.
+#[derive(Serialize, Deserialize)]
+pub struct BlocksRequest {
+    pub start_slot: Slot,
+    #[serde(default)]
+    pub end_slot: Option<Slot>,
+}
+
+/// Ledger entry of a produced block, chained from the previous blockhash.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BlockEntry {
+    pub num_hashes: u64,
+    pub hash: Hash,
+    pub signatures: Vec<Signature>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EncodedBlock {
+    pub slot: Slot,
+    #[serde(flatten)]
+    pub block: UiConfirmedBlock,
+    pub entries: Vec<BlockEntry>,
+}
+
+/// Executed transaction referencing an address.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SignatureInfo {
+    pub signature: Signature,
+    pub slot: Slot,
+    pub err: Option<TransactionError>,
+    pub memo: Option<String>,
+    pub block_time: Option<UnixTimestamp>,
+}
+
+/// Signatures referencing `address`, newest first, between the `before` and `until`
+/// signatures.
+#[derive(Serialize, Deserialize)]
+pub struct SignaturesForAddressRequest {
+    pub address: Pubkey,
+    #[serde(default)]
+    pub before: Option<Signature>,
+    #[serde(default)]
+    pub until: Option<Signature>,
+    #[serde(default = "default_signatures_for_address_limit")]
+    pub limit: usize,
+}
+
+fn default_signatures_for_address_limit() -> usize {
+    DEFAULT_SIGNATURES_FOR_ADDRESS_LIMIT
+}
+
+/// Account data decoded by one of the executor's account parsers.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ParsedAccount {
+    /// Parser which decoded the account, e.g. `spl-token` or the Anchor program's name.
+    pub program: String,
+    /// Account type, e.g. `mint` or the Anchor account's name.
+    pub account_type: String,
+    pub parsed: DecodedValue,
+}
+
+/// Account along with its decoded data, `None` if no parser recognized it.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ParsedAccountInfo {
+    pub account: Account,
+    pub parsed: Option<ParsedAccount>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DecodedInstruction {
+    pub name: String,
+    pub args: Vec<(String, DecodedValue)>,
+    /// Accounts named after the IDL, nested account groups are prefixed with the group name.
+    pub accounts: Vec<(String, Pubkey)>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DecodedEvent {
+    pub name: String,
+    pub fields: Vec<(String, DecodedValue)>,
+}
+
+/// Time spent in each processing stage of the bank, in microseconds.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct ExecutionTimings {
+    pub check_us: u64,
+    pub load_us: u64,
+    pub execute_us: u64,
+    pub store_us: u64,
+    pub serialize_us: u64,
+    pub create_vm_us: u64,
+    pub vm_execute_us: u64,
+    pub deserialize_us: u64,
+    pub get_or_create_executor_us: u64,
+}
+
+impl ExecutionTimings {
+    pub fn accumulate(&mut self, other: &ExecutionTimings) {
+        self.check_us += other.check_us;
+        self.load_us += other.load_us;
+        self.execute_us += other.execute_us;
+        self.store_us += other.store_us;
+        self.serialize_us += other.serialize_us;
+        self.create_vm_us += other.create_vm_us;
+        self.vm_execute_us += other.vm_execute_us;
+        self.deserialize_us += other.deserialize_us;
+        self.get_or_create_executor_us += other.get_or_create_executor_us;
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct InstructionProfile {
+    pub program_id: Pubkey,
+    pub stack_height: usize,
+    /// Units consumed by the instruction itself, excluding its inner instructions.
+    pub compute_units: u64,
+    /// Units consumed by the instruction and its inner instructions.
+    pub total_compute_units: u64,
+    pub inner_instructions: Vec<InstructionProfile>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ProgramProfile {
+    pub program_id: Pubkey,
+    pub invocations: u64,
+    /// Units consumed by the program itself, excluding the programs it invoked.
+    pub compute_units: u64,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct ExecutionProfile {
+    pub compute_units_consumed: u64,
+    pub instructions: Vec<InstructionProfile>,
+    pub programs: Vec<ProgramProfile>,
+    pub timings: ExecutionTimings,
+}
+
+/// Profiles of a batch's transactions, aggregated per program.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct BatchProfile {
+    pub compute_units_consumed: u64,
+    pub programs: Vec<ProgramProfile>,
+    pub timings: ExecutionTimings,
+}
+
+impl BatchProfile {
+    pub fn aggregate<'a>(profiles: impl IntoIterator<Item = &'a ExecutionProfile>) -> Self {
+        let mut batch_profile = BatchProfile::default();
+        for profile in profiles {
+            batch_profile.compute_units_consumed += profile.compute_units_consumed;
+            batch_profile.timings.accumulate(&profile.timings);
+            for program in &profile.programs {
+                match batch_profile
+                    .programs
+                    .iter_mut()
+                    .find(|p| p.program_id == program.program_id)
+                {
+                    Some(p) => {
+                        p.invocations += program.invocations;
+                        p.compute_units += program.compute_units;
+                    }
+                    None => batch_profile.programs.push(program.clone()),
+                }
+            }
+        }
+
+        batch_profile
+    }
+}
+
+/// Net balance changes of a transaction, or of a whole batch once aggregated.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct BalanceChanges {
+    pub sol: Vec<SolBalanceChange>,
+    pub tokens: Vec<TokenBalanceChange>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SolBalanceChange {
+    pub pubkey: Pubkey,
+    /// Change in lamports.
+    pub delta: i64,
+}
+
+/// Net change of an owner's balance of a mint, summed over all its token accounts.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TokenBalanceChange {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub decimals: u8,
+    /// Change in base units.
+    pub amount_delta: i128,
+    pub ui_amount_delta: f64,
+}
+
+impl TokenBalanceChange {
+    pub fn new(owner: Pubkey, mint: Pubkey, decimals: u8, amount_delta: i128) -> Self {
+        Self {
+            owner,
+            mint,
+            decimals,
+            amount_delta,
+            ui_amount_delta: amount_delta as f64 / 10f64.powi(decimals as i32),
+        }
+    }
+}
+
+impl BalanceChanges {
+    pub fn aggregate<'a>(changes: impl IntoIterator<Item = &'a BalanceChanges>) -> Self {
+        let mut batch_changes = BalanceChanges::default();
+        for changes in changes {
+            for sol in &changes.sol {
+                match batch_changes
+                    .sol
+                    .iter_mut()
+                    .find(|s| s.pubkey == sol.pubkey)
+                {
+                    Some(s) => s.delta += sol.delta,
+                    None => batch_changes.sol.push(sol.clone()),
+                }
+            }
+            for token in &changes.tokens {
+                match batch_changes
+                    .tokens
+                    .iter_mut()
+                    .find(|t| t.owner == token.owner && t.mint == token.mint)
+                {
+                    Some(t) => {
+                        *t = TokenBalanceChange::new(
+                            t.owner,
+                            t.mint,
+                            t.decimals,
+                            t.amount_delta + token.amount_delta,
+                        )
+                    }
+                    None => batch_changes.tokens.push(token.clone()),
+                }
+            }
+        }
+        batch_changes.sol.retain(|s| s.delta != 0);
+        batch_changes.tokens.retain(|t| t.amount_delta != 0);
+
+        batch_changes
+    }
+
+    /// Change in lamports of the given account, zero if it is unchanged.
+    pub fn sol_delta(&self, pubkey: &Pubkey) -> i64 {
+        self.sol
+            .iter()
+            .find(|s| &s.pubkey == pubkey)
+            .map_or(0, |s| s.delta)
+    }
+
+    /// Net change in base units of the owner's balance of the mint, zero if it is unchanged.
+    pub fn token_delta(&self, owner: &Pubkey, mint: &Pubkey) -> i128 {
+        self.tokens
+            .iter()
+            .find(|t| &t.owner == owner && &t.mint == mint)
+            .map_or(0, |t| t.amount_delta)
+    }
+}
+
+/// Profiles and balance changes of a batch's transactions, aggregated over the batch.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct BatchSummary {
+    pub profile: BatchProfile,
+    pub balance_changes: BalanceChanges,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct FeeBreakdown {
+    pub signature_fee: u64,
+    /// Fee paid for the compute unit price over the compute unit limit.
+    pub prioritization_fee: u64,
+    pub compute_unit_limit: u64,
+    /// Price per compute unit, in micro-lamports.
+    pub compute_unit_price: u64,
+    pub total_fee: u64,
+    pub rent_paid: u64,
+    pub payer_balance_delta: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EncodedExecutionResult {
+    #[serde(flatten)]
+    pub transaction: EncodedConfirmedTransactionWithStatusMeta,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_diffs: Option<Vec<AccountDiff>>,
+    pub call_tree: Vec<Invocation>,
+    pub profile: ExecutionProfile,
+    pub fee_breakdown: FeeBreakdown,
+    pub balance_changes: BalanceChanges,
+    pub decoded_error: Option<DecodedError>,
+}
+
+impl EncodedExecutionResult {
+    /// Top-level instructions, decoded when the result was requested as `jsonParsed`.
+    pub fn parsed_instructions(&self) -> Option<Vec<UiInstruction>> {
+        match &self.transaction.transaction.transaction {
+            EncodedTransaction::Json(UiTransaction {
+                message: UiMessage::Parsed(message),
+                ..
+            }) => Some(message.instructions.clone()),
+            _ => None,
+        }
+    }
+
+    /// Inner instructions grouped by top-level instruction index, decoded when the result was
+    /// requested as `jsonParsed`.
+    pub fn parsed_inner_instructions(&self) -> Option<Vec<UiInnerInstructions>> {
+        self.parsed_instructions()?;
+        let meta = self.transaction.transaction.meta.clone()?;
+        meta.inner_instructions.into()
+    }
+}
+
+/// Pending transactions executed in a block, in the sealed order.
+#[derive(Serialize, Deserialize)]
+pub struct EncodedSealedBlock {
+    pub slot: Slot,
+    pub results: Vec<EncodedExecutionResult>,
+}
+
+/// Rules a block engine enforces on bundles.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BundleRules {
+    pub max_transactions: usize,
+    /// Accounts the tip must be transferred to.
+    pub tip_accounts: Vec<Pubkey>,
+    pub min_tip_lamports: u64,
+}
+
+impl Default for BundleRules {
+    fn default() -> Self {
+        Self {
+            max_transactions: DEFAULT_MAX_BUNDLE_TRANSACTIONS,
+            tip_accounts: JITO_TIP_ACCOUNTS
+                .iter()
+                .map(|pubkey| Pubkey::from_str(pubkey).unwrap())
+                .collect(),
+            min_tip_lamports: DEFAULT_MIN_BUNDLE_TIP_LAMPORTS,
+        }
+    }
+}
+
+/// Bundle rule a simulated bundle breaks.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleViolation {
+    TooManyTransactions {
+        count: usize,
+        max: usize,
+    },
+    DuplicateSignature(Signature),
+    /// Tip accounts received less than the minimum, nothing at all if `tip_lamports` is zero.
+    /// Not reported for failed bundles, whose `TransactionFailed` violations already drop them.
+    InsufficientTip {
+        tip_lamports: u64,
+        min_tip_lamports: u64,
+    },
+    /// Bundles land all or nothing, a single failure drops the whole bundle.
+    TransactionFailed {
+        index: usize,
+        error: TransactionError,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BundleSimulationRequest {
+    pub bundle: Vec<Transaction>,
+    #[serde(default)]
+    pub account_overrides: Vec<(Pubkey, AccountOverride)>,
+    /// Jito's rules when unset.
+    #[serde(default)]
+    pub rules: Option<BundleRules>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BundleSimulation {
+    pub transactions: Vec<SimulatedTransaction>,
+    /// Lamports the tip accounts received over the bundle, zero if a transaction failed as the
+    /// bundle wouldn't land.
+    pub tip_lamports: u64,
+    /// Empty if the bundle would be accepted.
+    pub violations: Vec<BundleViolation>,
+}
+
+/// Accounts a transaction locks when executed.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AccountLocks {
+    pub writable: Vec<Pubkey>,
+    pub readonly: Vec<Pubkey>,
+}
+
+/// Transactions of a batch which can't execute in parallel, one of them writing to accounts
+/// the other one locks.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LockConflict {
+    pub first: usize,
+    pub second: usize,
+    pub accounts: Vec<Pubkey>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ScheduleAnalysis {
+    /// Locks of each transaction of the batch.
+    pub locks: Vec<AccountLocks>,
+    pub conflicts: Vec<LockConflict>,
+    /// Transactions executable in parallel, each group running after the previous one so
+    /// conflicting transactions keep the batch order.
+    pub groups: Vec<Vec<usize>>,
+}
+
+/// Differences between executing the groups of a batch in parallel and executing it
+/// sequentially.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ScheduleComparison {
+    pub parallel_errors: Vec<Option<TransactionError>>,
+    pub sequential_errors: Vec<Option<TransactionError>>,
+    /// Transactions whose status differs between both executions.
+    pub mismatched_transactions: Vec<usize>,
+    /// Locked accounts whose final state differs between both executions.
+    pub mismatched_accounts: Vec<Pubkey>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ScheduleAnalysisRequest {
+    pub batch: Vec<Transaction>,
+    /// Execute the groups on a fork and compare with a sequential execution.
+    #[serde(default)]
+    pub execute: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ScheduleReport {
+    pub analysis: ScheduleAnalysis,
+    pub comparison: Option<ScheduleComparison>,
+}
+
+/// Rendering of an account graph.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphFormat {
+    Json,
+    /// Graphviz DOT.
+    Dot,
+}
+
+impl Default for GraphFormat {
+    fn default() -> Self {
+        GraphFormat::Json
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TransactionNode {
+    pub index: usize,
+    pub signature: Signature,
+    pub error: Option<TransactionError>,
+}
+
+/// Account referenced by the batch, with the roles it plays across its transactions.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AccountNode {
+    pub pubkey: Pubkey,
+    pub is_program: bool,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountAccess {
+    Signs,
+    Reads,
+    Writes,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AccountEdge {
+    pub transaction: usize,
+    pub account: Pubkey,
+    pub access: AccountAccess,
+}
+
+/// Program invoked by a transaction's instruction, or by another program through a CPI.
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct InvocationEdge {
+    pub transaction: usize,
+    /// Invoking program, none for top-level instructions.
+    pub caller: Option<Pubkey>,
+    pub program_id: Pubkey,
+    pub stack_height: usize,
+}
+
+/// Transactions of a batch, the programs they invoke and the accounts they access.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct AccountGraph {
+    pub transactions: Vec<TransactionNode>,
+    pub accounts: Vec<AccountNode>,
+    pub account_edges: Vec<AccountEdge>,
+    pub invocations: Vec<InvocationEdge>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AccountGraphRequest {
+    pub batch: Vec<Transaction>,
+    #[serde(default)]
+    pub account_overrides: Vec<(Pubkey, AccountOverride)>,
+    #[serde(default)]
+    pub format: GraphFormat,
+}
+
+/// How the orderings of the candidates are explored.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchStrategy {
+    /// Every permutation of the candidates, up to `MAX_EXHAUSTIVE_SEARCH_CANDIDATES` of them.
+    Exhaustive,
+    /// Orderings built one transaction at a time, keeping the `width` most profitable
+    /// prefixes at each step. A width of one is a greedy search, widths are capped to
+    /// `MAX_BEAM_WIDTH`.
+    Beam { width: usize },
+}
+
+impl SearchStrategy {
+    /// Exhaustive for small candidate sets, beam search otherwise.
+    pub fn for_candidates(len: usize) -> Self {
+        if len <= DEFAULT_EXHAUSTIVE_SEARCH_LIMIT {
+            SearchStrategy::Exhaustive
+        } else {
+            SearchStrategy::Beam {
+                width: DEFAULT_BEAM_WIDTH,
+            }
+        }
+    }
+}
+
+/// Search of the ordering of the candidates maximizing the value gained by `wallet`.
+#[derive(Serialize, Deserialize)]
+pub struct OrderingSearchRequest {
+    pub candidates: Vec<Transaction>,
+    pub wallet: Pubkey,
+    /// Value in lamports of one base unit of each mint, tokens without a price aren't valued.
+    #[serde(default)]
+    pub token_prices: Vec<(Pubkey, f64)>,
+    /// Picked from the number of candidates when unset.
+    #[serde(default)]
+    pub strategy: Option<SearchStrategy>,
+}
+
+/// Outcome of the candidates executed in a given order.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OrderingOutcome {
+    /// Indexes of the executed candidates, in execution order.
+    pub ordering: Vec<usize>,
+    /// SOL delta of the wallet plus the value of its token deltas, in lamports.
+    pub profit: f64,
+    pub sol_delta: i64,
+    pub token_deltas: Vec<TokenBalanceChange>,
+    /// Error of each executed candidate, in execution order.
+    pub errors: Vec<Option<TransactionError>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct OrderingSearchResult {
+    /// Most profitable ordering of all the candidates.
+    pub best: OrderingOutcome,
+    /// Every evaluated ordering, including the prefixes explored by a beam search.
+    pub outcomes: Vec<OrderingOutcome>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SimulatedTransaction {
+    pub transaction: EncodedExecutionResult,
+    pub post_accounts: Vec<(Pubkey, Option<Account>)>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EncodedBatchResult {
+    pub results: Vec<EncodedExecutionResult>,
+    pub summary: BatchSummary,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SimulatedBatch {
+    pub transactions: Vec<SimulatedTransaction>,
+    pub summary: BatchSummary,
+}
+
 pub type ClientResult<T> = Result<T, reqwest::Error>;
 
 impl Default for ExecutorClient {
@@ -90,6 +990,62 @@ impl ExecutorClient {
             .send()
     }
 
+    pub fn set_block_production(
+        &self,
+        block_production: BlockProduction,
+    ) -> ClientResult<reqwest::blocking::Response> {
+        self.http_client
+            .post(self.build_url("/set_block_production"))
+            .json(&block_production)
+            .send()
+    }
+
+    /// Closes the working bank's block, returning its slot.
+    pub fn close_block(&self) -> ClientResult<Slot> {
+        self.http_client
+            .post(self.build_url("/close_block"))
+            .send()?
+            .json::<Slot>()
+    }
+
+    pub fn get_block(&self, slot: Slot) -> ClientResult<Option<EncodedBlock>> {
+        self.get_block_with_config(slot, EncodingConfig::default())
+    }
+
+    pub fn get_block_with_config(
+        &self,
+        slot: Slot,
+        config: EncodingConfig,
+    ) -> ClientResult<Option<EncodedBlock>> {
+        self.http_client
+            .get(self.build_url("/get_block"))
+            .query(&config)
+            .json(&slot)
+            .send()?
+            .json::<Option<EncodedBlock>>()
+    }
+
+    pub fn get_blocks(&self, start_slot: Slot, end_slot: Option<Slot>) -> ClientResult<Vec<Slot>> {
+        self.http_client
+            .get(self.build_url("/get_blocks"))
+            .json(&BlocksRequest {
+                start_slot,
+                end_slot,
+            })
+            .send()?
+            .json::<Vec<Slot>>()
+    }
+
+    pub fn set_compute_budget_overrides(
+        &self,
+        compute_budget_overrides: Option<ComputeBudgetOverrides>,
+    ) -> ClientResult<reqwest::blocking::Response> {
+        self.http_client
+            .post(self.build_url("/set_compute_budget_overrides"))
+            .json(&compute_budget_overrides)
+            .send()
+    }
+
     pub fn get_rent_exempt_balance(&self, data_length: usize) -> ClientResult<u64> {
         self.http_client
             .get(self.build_url("/rent_exempt_balance"))
@@ -114,15 +1070,364 @@ impl ExecutorClient {
             .json::<Vec<Option<Account>>>()
     }
 
+    pub fn get_program_accounts(
+        &self,
+        program_id: Pubkey,
+        filters: Vec<AccountFilter>,
+    ) -> ClientResult<Vec<(Pubkey, Account)>> {
+        self.http_client
+            .get(self.build_url("/get_program_accounts"))
+            .json(&GetProgramAccountsRequest {
+                program_id,
+                filters,
+            })
+            .send()?
+            .json::<Vec<(Pubkey, Account)>>()
+    }
+
+    /// Initialized token accounts of the owner, failing if the executor lacks one of their
+    /// mints.
+    pub fn get_token_accounts_by_owner(
+        &self,
+        owner: Pubkey,
+        mint: Option<Pubkey>,
+    ) -> ClientResult<Vec<TokenAccountBalance>> {
+        self.http_client
+            .get(self.build_url("/get_token_accounts_by_owner"))
+            .json(&TokenAccountsRequest {
+                pubkey: owner,
+                mint,
+            })
+            .send()?
+            .json::<Vec<TokenAccountBalance>>()
+    }
+
+    /// Initialized token accounts of the delegate, failing like `get_token_accounts_by_owner`.
+    pub fn get_token_accounts_by_delegate(
+        &self,
+        delegate: Pubkey,
+        mint: Option<Pubkey>,
+    ) -> ClientResult<Vec<TokenAccountBalance>> {
+        self.http_client
+            .get(self.build_url("/get_token_accounts_by_delegate"))
+            .json(&TokenAccountsRequest {
+                pubkey: delegate,
+                mint,
+            })
+            .send()?
+            .json::<Vec<TokenAccountBalance>>()
+    }
+
+    pub fn get_token_largest_accounts(
+        &self,
+        mint: Pubkey,
+        limit: usize,
+    ) -> ClientResult<Vec<TokenAccountBalance>> {
+        self.http_client
+            .get(self.build_url("/get_token_largest_accounts"))
+            .json(&LargestTokenAccountsRequest { mint, limit })
+            .send()?
+            .json::<Vec<TokenAccountBalance>>()
+    }
+
+    pub fn get_parsed_account(&self, pubkey: &Pubkey) -> ClientResult<Option<ParsedAccountInfo>> {
+        self.http_client
+            .get(self.build_url("/get_parsed_account"))
+            .json(pubkey)
+            .send()?
+            .json::<Option<ParsedAccountInfo>>()
+    }
+
+    pub fn get_parsed_accounts(
+        &self,
+        pubkeys: &Vec<Pubkey>,
+    ) -> ClientResult<Vec<Option<ParsedAccountInfo>>> {
+        self.http_client
+            .get(self.build_url("/get_parsed_accounts"))
+            .json(pubkeys)
+            .send()?
+            .json::<Vec<Option<ParsedAccountInfo>>>()
+    }
+
+    pub fn get_transaction(
+        &self,
+        signature: &Signature,
+    ) -> ClientResult<Option<EncodedConfirmedTransactionWithStatusMeta>> {
+        self.get_transaction_with_config(signature, EncodingConfig::default())
+    }
+
+    pub fn get_transaction_with_config(
+        &self,
+        signature: &Signature,
+        config: EncodingConfig,
+    ) -> ClientResult<Option<EncodedConfirmedTransactionWithStatusMeta>> {
+        self.http_client
+            .get(self.build_url("/get_transaction"))
+            .query(&config)
+            .json(signature)
+            .send()?
+            .json::<Option<EncodedConfirmedTransactionWithStatusMeta>>()
+    }
+
+    pub fn get_signature_statuses(
+        &self,
+        signatures: &[Signature],
+    ) -> ClientResult<Vec<Option<TransactionStatus>>> {
+        self.http_client
+            .get(self.build_url("/get_signature_statuses"))
+            .json(signatures)
+            .send()?
+            .json::<Vec<Option<TransactionStatus>>>()
+    }
+
+    pub fn get_signatures_for_address(
+        &self,
+        address: Pubkey,
+        before: Option<Signature>,
+        until: Option<Signature>,
+        limit: usize,
+    ) -> ClientResult<Vec<SignatureInfo>> {
+        self.http_client
+            .get(self.build_url("/get_signatures_for_address"))
+            .json(&SignaturesForAddressRequest {
+                address,
+                before,
+                until,
+                limit,
+            })
+            .send()?
+            .json::<Vec<SignatureInfo>>()
+    }
+
     pub fn execute_transaction_batch(
         &self,
         batch: Vec<Transaction>,
-    ) -> ClientResult<Vec<EncodedConfirmedTransactionWithStatusMeta>> {
+    ) -> ClientResult<EncodedBatchResult> {
+        self.execute_transaction_batch_with_config(batch, EncodingConfig::default())
+    }
+
+    pub fn execute_transaction_batch_with_config(
+        &self,
+        batch: Vec<Transaction>,
+        config: EncodingConfig,
+    ) -> ClientResult<EncodedBatchResult> {
         self.http_client
             .post(self.build_url("/execute_transaction_batch"))
+            .query(&config)
+            .json(&batch)
+            .send()?
+            .json::<EncodedBatchResult>()
+    }
+
+    /// Queues transactions for the next sealed block, without executing them. Submission stops
+    /// at the first unsigned transaction, the previous ones staying queued.
+    pub fn submit(&self, batch: Vec<Transaction>) -> ClientResult<Vec<Signature>> {
+        self.http_client
+            .post(self.build_url("/submit"))
             .json(&batch)
             .send()?
-            .json::<Vec<EncodedConfirmedTransactionWithStatusMeta>>()
+            .json::<Vec<Signature>>()
+    }
+
+    pub fn pending(&self) -> ClientResult<Vec<Transaction>> {
+        self.http_client
+            .get(self.build_url("/pending"))
+            .send()?
+            .json::<Vec<Transaction>>()
+    }
+
+    /// Executes the pending transactions in the order of the policy, then closes the block.
+    pub fn seal_block(&self, ordering: OrderingPolicy) -> ClientResult<EncodedSealedBlock> {
+        self.seal_block_with_config(ordering, EncodingConfig::default())
+    }
+
+    pub fn seal_block_with_config(
+        &self,
+        ordering: OrderingPolicy,
+        config: EncodingConfig,
+    ) -> ClientResult<EncodedSealedBlock> {
+        self.http_client
+            .post(self.build_url("/seal_block"))
+            .query(&config)
+            .json(&ordering)
+            .send()?
+            .json::<EncodedSealedBlock>()
+    }
+
+    pub fn simulate_transaction_batch(
+        &self,
+        batch: Vec<Transaction>,
+        account_overrides: Vec<(Pubkey, AccountOverride)>,
+    ) -> ClientResult<SimulatedBatch> {
+        self.simulate_transaction_batch_with_config(
+            batch,
+            account_overrides,
+            EncodingConfig::default(),
+        )
+    }
+
+    pub fn simulate_transaction_batch_with_config(
+        &self,
+        batch: Vec<Transaction>,
+        account_overrides: Vec<(Pubkey, AccountOverride)>,
+        config: EncodingConfig,
+    ) -> ClientResult<SimulatedBatch> {
+        self.http_client
+            .post(self.build_url("/simulate_transaction_batch"))
+            .query(&config)
+            .json(&SimulationRequest {
+                batch,
+                account_overrides,
+            })
+            .send()?
+            .json::<SimulatedBatch>()
+    }
+
+    /// Simulates the bundle like `simulate_transaction_batch`, checking it against the bundle
+    /// rules, Jito's ones by default.
+    pub fn simulate_bundle(
+        &self,
+        bundle: Vec<Transaction>,
+        account_overrides: Vec<(Pubkey, AccountOverride)>,
+        rules: Option<BundleRules>,
+    ) -> ClientResult<BundleSimulation> {
+        self.simulate_bundle_with_config(
+            bundle,
+            account_overrides,
+            rules,
+            EncodingConfig::default(),
+        )
+    }
+
+    pub fn simulate_bundle_with_config(
+        &self,
+        bundle: Vec<Transaction>,
+        account_overrides: Vec<(Pubkey, AccountOverride)>,
+        rules: Option<BundleRules>,
+        config: EncodingConfig,
+    ) -> ClientResult<BundleSimulation> {
+        self.http_client
+            .post(self.build_url("/simulate_bundle"))
+            .query(&config)
+            .json(&BundleSimulationRequest {
+                bundle,
+                account_overrides,
+                rules,
+            })
+            .send()?
+            .json::<BundleSimulation>()
+    }
+
+    pub fn register_program_errors(
+        &self,
+        program_id: Pubkey,
+        errors: Vec<ProgramErrorDefinition>,
+    ) -> ClientResult<reqwest::blocking::Response> {
+        self.http_client
+            .post(self.build_url("/register_program_errors"))
+            .json(&RegisterProgramErrorsRequest { program_id, errors })
+            .send()
+    }
+
+    /// Names the Anchor framework error codes of a program without registering its IDL.
+    pub fn register_anchor_program(
+        &self,
+        program_id: &Pubkey,
+    ) -> ClientResult<reqwest::blocking::Response> {
+        self.http_client
+            .post(self.build_url("/register_anchor_program"))
+            .json(program_id)
+            .send()
+    }
+
+    pub fn register_idl(
+        &self,
+        program_id: Pubkey,
+        idl: Option<String>,
+    ) -> ClientResult<reqwest::blocking::Response> {
+        self.http_client
+            .post(self.build_url("/register_idl"))
+            .json(&RegisterIdlRequest { program_id, idl })
+            .send()
+    }
+
+    /// Evaluates orderings of the candidates on forks of the working bank, returning the one
+    /// maximizing the value gained by the wallet.
+    pub fn search_ordering(
+        &self,
+        request: OrderingSearchRequest,
+    ) -> ClientResult<OrderingSearchResult> {
+        self.http_client
+            .post(self.build_url("/search_ordering"))
+            .json(&request)
+            .send()?
+            .json::<OrderingSearchResult>()
+    }
+
+    /// Computes the lock conflicts of the batch and the groups a leader could execute in
+    /// parallel, optionally comparing their execution with a sequential one.
+    pub fn analyze_schedule(
+        &self,
+        batch: Vec<Transaction>,
+        execute: bool,
+    ) -> ClientResult<ScheduleReport> {
+        self.http_client
+            .post(self.build_url("/analyze_schedule"))
+            .json(&ScheduleAnalysisRequest { batch, execute })
+            .send()?
+            .json::<ScheduleReport>()
+    }
+
+    /// Simulates the batch and returns the graph of its transactions, invoked programs and
+    /// accessed accounts.
+    pub fn get_account_graph(
+        &self,
+        batch: Vec<Transaction>,
+        account_overrides: Vec<(Pubkey, AccountOverride)>,
+    ) -> ClientResult<AccountGraph> {
+        self.http_client
+            .post(self.build_url("/account_graph"))
+            .json(&AccountGraphRequest {
+                batch,
+                account_overrides,
+                format: GraphFormat::Json,
+            })
+            .send()?
+            .json::<AccountGraph>()
+    }
+
+    /// Same as `get_account_graph`, rendered as Graphviz DOT.
+    pub fn get_account_graph_dot(
+        &self,
+        batch: Vec<Transaction>,
+        account_overrides: Vec<(Pubkey, AccountOverride)>,
+    ) -> ClientResult<String> {
+        self.http_client
+            .post(self.build_url("/account_graph"))
+            .json(&AccountGraphRequest {
+                batch,
+                account_overrides,
+                format: GraphFormat::Dot,
+            })
+            .send()?
+            .text()
+    }
+
+    /// Fails on the compute budget requests a leader rejects, like duplicate ComputeBudget
+    /// instructions.
+    pub fn estimate_compute_units(
+        &self,
+        transaction: Transaction,
+        margin_percent: u64,
+    ) -> ClientResult<ComputeUnitsEstimate> {
+        self.http_client
+            .post(self.build_url("/estimate_compute_units"))
+            .json(&ComputeUnitsEstimateRequest {
+                transaction,
+                margin_percent,
+            })
+            .send()?
+            .json::<ComputeUnitsEstimate>()
     }
 
     fn build_url(&self, path: &str) -> Url {
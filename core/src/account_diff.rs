@@ -0,0 +1,136 @@
+use executor_client::{AccountDiff, DataChange};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+/// Diffs the state of an account before and after a transaction, `None` if it is unchanged.
+pub fn diff_account(
+    pubkey: Pubkey,
+    pre: Option<&Account>,
+    post: Option<&Account>,
+) -> Option<AccountDiff> {
+    let empty = Account::default();
+    let pre = pre.unwrap_or(&empty);
+    let post = post.unwrap_or(&empty);
+    if pre == post {
+        return None;
+    }
+
+    Some(AccountDiff {
+        pubkey,
+        pre_lamports: pre.lamports,
+        post_lamports: post.lamports,
+        owner_change: (pre.owner != post.owner).then(|| (pre.owner, post.owner)),
+        pre_data_len: pre.data.len(),
+        post_data_len: post.data.len(),
+        data_changes: data_changes(&pre.data, &post.data),
+    })
+}
+
+fn data_changes(pre: &[u8], post: &[u8]) -> Vec<DataChange> {
+    let common_len = pre.len().min(post.len());
+    let mut changes: Vec<DataChange> = vec![];
+    let mut offset = 0;
+    while offset < common_len {
+        if pre[offset] == post[offset] {
+            offset += 1;
+            continue;
+        }
+        let start = offset;
+        while offset < common_len && pre[offset] != post[offset] {
+            offset += 1;
+        }
+        changes.push(DataChange {
+            offset: start,
+            pre: pre[start..offset].to_vec(),
+            post: post[start..offset].to_vec(),
+        });
+    }
+
+    // Resized data, report the tail as changed and merge it with an adjacent run
+    if pre.len() != post.len() {
+        match changes.last_mut() {
+            Some(last) if last.offset + last.pre.len() == common_len => {
+                last.pre.extend_from_slice(&pre[common_len..]);
+                last.post.extend_from_slice(&post[common_len..]);
+            }
+            _ => changes.push(DataChange {
+                offset: common_len,
+                pre: pre[common_len..].to_vec(),
+                post: post[common_len..].to_vec(),
+            }),
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(lamports: u64, data: Vec<u8>) -> Account {
+        Account {
+            lamports,
+            data,
+            ..Account::default()
+        }
+    }
+
+    fn ranges(changes: &[DataChange]) -> Vec<(usize, Vec<u8>, Vec<u8>)> {
+        changes
+            .iter()
+            .map(|change| (change.offset, change.pre.clone(), change.post.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_unchanged_account() {
+        let pubkey = Pubkey::new_unique();
+        let pre = account(1, vec![1, 2]);
+        assert!(diff_account(pubkey, Some(&pre), Some(&pre.clone())).is_none());
+        assert!(diff_account(pubkey, None, None).is_none());
+    }
+
+    #[test]
+    fn test_changed_runs() {
+        let pre = account(1, vec![0, 1, 2, 3, 4, 5]);
+        let post = account(2, vec![0, 9, 9, 3, 4, 8]);
+        let diff = diff_account(Pubkey::new_unique(), Some(&pre), Some(&post)).unwrap();
+        assert_eq!(diff.pre_lamports, 1);
+        assert_eq!(diff.post_lamports, 2);
+        assert!(diff.owner_change.is_none());
+        assert_eq!(
+            ranges(&diff.data_changes),
+            vec![(1, vec![1, 2], vec![9, 9]), (5, vec![5], vec![8])]
+        );
+    }
+
+    #[test]
+    fn test_resized_data_merges_adjacent_run() {
+        let pre = account(1, vec![0, 1, 2]);
+        let post = account(1, vec![0, 1, 7, 8, 9]);
+        let diff = diff_account(Pubkey::new_unique(), Some(&pre), Some(&post)).unwrap();
+        assert_eq!((diff.pre_data_len, diff.post_data_len), (3, 5));
+        assert_eq!(
+            ranges(&diff.data_changes),
+            vec![(2, vec![2], vec![7, 8, 9])]
+        );
+
+        let shrunk = account(1, vec![0]);
+        let diff = diff_account(Pubkey::new_unique(), Some(&pre), Some(&shrunk)).unwrap();
+        assert_eq!(ranges(&diff.data_changes), vec![(1, vec![1, 2], vec![])]);
+    }
+
+    #[test]
+    fn test_created_and_closed_accounts() {
+        let owner = Pubkey::new_unique();
+        let created = Account::new(5, 2, &owner);
+        let diff = diff_account(Pubkey::new_unique(), None, Some(&created)).unwrap();
+        assert_eq!(diff.pre_lamports, 0);
+        assert_eq!(diff.owner_change, Some((Pubkey::default(), owner)));
+        assert_eq!(ranges(&diff.data_changes), vec![(0, vec![], vec![0, 0])]);
+
+        let diff = diff_account(Pubkey::new_unique(), Some(&created), None).unwrap();
+        assert_eq!(diff.post_lamports, 0);
+        assert_eq!(diff.owner_change, Some((owner, Pubkey::default())));
+    }
+}
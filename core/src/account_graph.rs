@@ -0,0 +1,276 @@
+use crate::result::ExecutionResult;
+use executor_client::{
+    AccountAccess, AccountEdge, AccountGraph, AccountNode, Invocation, InvocationEdge,
+    TransactionNode,
+};
+use itertools::Itertools;
+use solana_sdk::pubkey::Pubkey;
+use std::{collections::HashMap, fmt::Write};
+
+/// Builds the graph of the executed transactions, the programs they invoked, including through
+/// CPIs, and the accounts they signed with, read and wrote.
+pub fn build_account_graph(results: &[ExecutionResult]) -> AccountGraph {
+    let mut graph = AccountGraph::default();
+    let mut account_indexes: HashMap<Pubkey, usize> = HashMap::new();
+    let mut account_node = |graph: &mut AccountGraph, pubkey: Pubkey| -> usize {
+        *account_indexes.entry(pubkey).or_insert_with(|| {
+            graph.accounts.push(AccountNode {
+                pubkey,
+                is_program: false,
+                is_signer: false,
+                is_writable: false,
+            });
+            graph.accounts.len() - 1
+        })
+    };
+
+    for (index, result) in results.iter().enumerate() {
+        let tx = result
+            .transaction
+            .clone()
+            .into_legacy_transaction()
+            .expect("Executed transactions must be legacy ones");
+        graph.transactions.push(TransactionNode {
+            index,
+            // Unsigned transactions keep a node, identified by their index
+            signature: tx.signatures.first().copied().unwrap_or_default(),
+            error: result.status.clone().err(),
+        });
+
+        for (key_index, pubkey) in tx.message.account_keys.iter().enumerate() {
+            let node = account_node(&mut graph, *pubkey);
+            let mut accesses = vec![];
+            if tx.message.is_signer(key_index) {
+                graph.accounts[node].is_signer = true;
+                accesses.push(AccountAccess::Signs);
+            }
+            // Same demotion of program ids and sysvars as the account locks
+            if tx.message.is_writable(key_index) {
+                graph.accounts[node].is_writable = true;
+                accesses.push(AccountAccess::Writes);
+            } else {
+                accesses.push(AccountAccess::Reads);
+            }
+            graph
+                .account_edges
+                .extend(accesses.into_iter().map(|access| AccountEdge {
+                    transaction: index,
+                    account: *pubkey,
+                    access,
+                }));
+        }
+
+        let mut invocations = vec![];
+        collect_invocations(index, None, &result.call_tree, &mut invocations);
+        for invocation in invocations.into_iter().unique() {
+            let node = account_node(&mut graph, invocation.program_id);
+            graph.accounts[node].is_program = true;
+            graph.invocations.push(invocation);
+        }
+    }
+
+    graph
+}
+
+fn collect_invocations(
+    transaction: usize,
+    caller: Option<Pubkey>,
+    call_tree: &[Invocation],
+    edges: &mut Vec<InvocationEdge>,
+) {
+    for invocation in call_tree {
+        edges.push(InvocationEdge {
+            transaction,
+            caller,
+            program_id: invocation.program_id,
+            stack_height: invocation.stack_height,
+        });
+        collect_invocations(
+            transaction,
+            Some(invocation.program_id),
+            &invocation.inner_invocations,
+            edges,
+        );
+    }
+}
+
+/// Renders the graph as Graphviz DOT: transactions are boxes, pink when they failed, programs
+/// are components and accounts are ellipses, bold for signers.
+pub fn render_dot(graph: &AccountGraph) -> String {
+    let mut dot = String::new();
+    writeln!(dot, "digraph accounts {{").unwrap();
+    writeln!(dot, "    rankdir=LR;").unwrap();
+    writeln!(dot, "    node [fontname=\"monospace\"];").unwrap();
+
+    for transaction in &graph.transactions {
+        writeln!(
+            dot,
+            "    \"tx{}\" [label=\"#{} {}\", shape=box, style=filled, fillcolor={}];",
+            transaction.index,
+            transaction.index,
+            abbreviate(&transaction.signature.to_string()),
+            match transaction.error {
+                Some(_) => "lightpink",
+                None => "lightblue",
+            }
+        )
+        .unwrap();
+    }
+
+    for account in &graph.accounts {
+        writeln!(
+            dot,
+            "    \"{}\" [label=\"{}\", shape={}{}];",
+            account.pubkey,
+            abbreviate(&account.pubkey.to_string()),
+            if account.is_program {
+                "component"
+            } else {
+                "ellipse"
+            },
+            if account.is_signer {
+                ", style=bold"
+            } else {
+                ""
+            },
+        )
+        .unwrap();
+    }
+
+    for edge in &graph.account_edges {
+        let attributes = match edge.access {
+            AccountAccess::Signs => "label=\"signs\", style=dashed",
+            AccountAccess::Reads => "label=\"reads\", color=gray",
+            AccountAccess::Writes => "label=\"writes\", color=red",
+        };
+        writeln!(
+            dot,
+            "    \"tx{}\" -> \"{}\" [{}];",
+            edge.transaction, edge.account, attributes
+        )
+        .unwrap();
+    }
+
+    for invocation in &graph.invocations {
+        match invocation.caller {
+            None => writeln!(
+                dot,
+                "    \"tx{}\" -> \"{}\" [label=\"invokes\", color=blue];",
+                invocation.transaction, invocation.program_id
+            ),
+            Some(caller) => writeln!(
+                dot,
+                "    \"{}\" -> \"{}\" [label=\"CPI tx#{}\", color=blue, style=bold];",
+                caller, invocation.program_id, invocation.transaction
+            ),
+        }
+        .unwrap();
+    }
+
+    writeln!(dot, "}}").unwrap();
+    dot
+}
+
+/// First and last characters of a base58 string, for readable labels.
+fn abbreviate(value: &str) -> String {
+    if value.len() <= 11 {
+        return value.to_string();
+    }
+    format!("{}…{}", &value[..4], &value[value.len() - 4..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{
+        hash::Hash,
+        signature::{Keypair, Signer},
+        system_instruction,
+        transaction::{Transaction, TransactionError, VersionedTransaction},
+    };
+
+    fn invocation(
+        program_id: Pubkey,
+        stack_height: usize,
+        inner_invocations: Vec<Invocation>,
+    ) -> Invocation {
+        Invocation {
+            program_id,
+            data: vec![],
+            accounts: vec![],
+            stack_height,
+            compute_units_consumed: None,
+            logs: vec![],
+            error: None,
+            decoded_instruction: None,
+            events: vec![],
+            inner_invocations,
+        }
+    }
+
+    #[test]
+    fn test_abbreviate() {
+        assert_eq!(abbreviate("short"), "short");
+        assert_eq!(abbreviate("12345678901"), "12345678901");
+        assert_eq!(abbreviate("123456789012"), "1234…9012");
+    }
+
+    #[test]
+    fn test_render_dot() {
+        let payer = Keypair::new();
+        let recipient = Pubkey::new_unique();
+        let tx = Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(&payer.pubkey(), &recipient, 1)],
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::default(),
+        );
+        let (program_id, callee) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut result = ExecutionResult::for_tests(VersionedTransaction::from(tx.clone()), 0);
+        result.status = Err(TransactionError::AccountNotFound);
+        result.call_tree = vec![invocation(
+            program_id,
+            1,
+            vec![invocation(callee, 2, vec![])],
+        )];
+
+        let graph = build_account_graph(&[result]);
+        assert_eq!(graph.transactions.len(), 1);
+        assert_eq!(graph.invocations.len(), 2);
+        let dot = render_dot(&graph);
+        let signature = tx.signatures[0].to_string();
+
+        assert!(dot.starts_with("digraph accounts {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains(&format!(
+            "    \"tx0\" [label=\"#0 {}\", shape=box, style=filled, fillcolor=lightpink];\n",
+            abbreviate(&signature)
+        )));
+        assert!(dot.contains(&format!(
+            "    \"{}\" [label=\"{}\", shape=ellipse, style=bold];\n",
+            payer.pubkey(),
+            abbreviate(&payer.pubkey().to_string())
+        )));
+        assert!(dot.contains(&format!(
+            "    \"{}\" [label=\"{}\", shape=component];\n",
+            callee,
+            abbreviate(&callee.to_string())
+        )));
+        assert!(dot.contains(&format!(
+            "    \"tx0\" -> \"{}\" [label=\"signs\", style=dashed];\n",
+            payer.pubkey()
+        )));
+        assert!(dot.contains(&format!(
+            "    \"tx0\" -> \"{}\" [label=\"writes\", color=red];\n",
+            recipient
+        )));
+        assert!(dot.contains(&format!(
+            "    \"tx0\" -> \"{}\" [label=\"invokes\", color=blue];\n",
+            program_id
+        )));
+        assert!(dot.contains(&format!(
+            "    \"{}\" -> \"{}\" [label=\"CPI tx#0\", color=blue, style=bold];\n",
+            program_id, callee
+        )));
+    }
+}
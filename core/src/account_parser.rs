@@ -0,0 +1,86 @@
+use crate::idl::{decode_fields, IdlField, IdlTypeDefinition};
+use executor_client::{DecodedValue, ParsedAccount};
+use serde_json::Value;
+use solana_account_decoder::parse_account_data::{parse_account_data, AccountAdditionalData};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+/// Decodes the data of accounts owned by a given program.
+pub trait AccountParser: Send + Sync {
+    fn parse_account(&self, pubkey: &Pubkey, account: &Account) -> Option<ParsedAccount>;
+}
+
+/// Account type described by its borsh layout, for programs without an Anchor IDL.
+pub struct BorshAccountLayout {
+    pub program: String,
+    pub account_type: String,
+    /// Prefix identifying the account type, if the program uses one.
+    pub discriminator: Vec<u8>,
+    pub fields: Vec<IdlField>,
+    /// Types the fields may refer to.
+    pub types: Vec<IdlTypeDefinition>,
+}
+
+impl AccountParser for BorshAccountLayout {
+    fn parse_account(&self, _pubkey: &Pubkey, account: &Account) -> Option<ParsedAccount> {
+        let mut data = account.data.strip_prefix(self.discriminator.as_slice())?;
+        Some(ParsedAccount {
+            program: self.program.clone(),
+            account_type: self.account_type.clone(),
+            parsed: DecodedValue::Struct(decode_fields(&self.types, &self.fields, &mut data).ok()?),
+        })
+    }
+}
+
+/// Decodes the accounts RPC nodes parse as `jsonParsed`: SPL Token accounts and mints,
+/// upgradeable loader state, sysvars, nonce, stake, vote and config accounts.
+pub fn parse_builtin_account(
+    pubkey: &Pubkey,
+    account: &Account,
+    spl_token_decimals: Option<u8>,
+) -> Option<ParsedAccount> {
+    let parsed = parse_account_data(
+        pubkey,
+        &account.owner,
+        &account.data,
+        Some(AccountAdditionalData { spl_token_decimals }),
+    )
+    .ok()?;
+
+    // Parsed accounts are tagged as `{ "type": ..., "info": ... }`
+    let (account_type, info) = match parsed.parsed {
+        Value::Object(mut object) => (
+            object
+                .remove("type")
+                .and_then(|ty| ty.as_str().map(str::to_string))
+                .unwrap_or_default(),
+            object.remove("info").unwrap_or(Value::Object(object)),
+        ),
+        value => (String::new(), value),
+    };
+
+    Some(ParsedAccount {
+        program: parsed.program,
+        account_type,
+        parsed: from_json(info),
+    })
+}
+
+fn from_json(value: Value) -> DecodedValue {
+    match value {
+        Value::Null => DecodedValue::Option(None),
+        Value::Bool(value) => DecodedValue::Bool(value),
+        Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+            (Some(value), _) => DecodedValue::Unsigned(value as u128),
+            (None, Some(value)) => DecodedValue::Signed(value as i128),
+            (None, None) => DecodedValue::Float(number.as_f64().unwrap_or_default()),
+        },
+        Value::String(value) => DecodedValue::String(value),
+        Value::Array(values) => DecodedValue::Array(values.into_iter().map(from_json).collect()),
+        Value::Object(object) => DecodedValue::Struct(
+            object
+                .into_iter()
+                .map(|(key, value)| (key, from_json(value)))
+                .collect(),
+        ),
+    }
+}
@@ -0,0 +1,142 @@
+use executor_client::{BalanceChanges, SolBalanceChange, TokenBalanceChange};
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::TransactionTokenBalance;
+use std::collections::BTreeMap;
+
+/// Nets the SOL balance changes per account and the token balance changes per (owner, mint).
+pub fn build_balance_changes(
+    account_keys: &[Pubkey],
+    pre_balances: &[u64],
+    post_balances: &[u64],
+    pre_token_balances: &[TransactionTokenBalance],
+    post_token_balances: &[TransactionTokenBalance],
+) -> BalanceChanges {
+    let sol = account_keys
+        .iter()
+        .zip(pre_balances.iter().zip(post_balances))
+        .filter(|(_, (pre, post))| pre != post)
+        .map(|(pubkey, (pre, post))| SolBalanceChange {
+            pubkey: *pubkey,
+            delta: *post as i64 - *pre as i64,
+        })
+        .collect();
+
+    let mut token_deltas: BTreeMap<(Pubkey, Pubkey), (u8, i128)> = BTreeMap::new();
+    for (balances, sign) in [(pre_token_balances, -1), (post_token_balances, 1)] {
+        for balance in balances {
+            if let Some((owner, mint, amount)) = parse_token_balance(balance) {
+                let (_, delta) = token_deltas
+                    .entry((owner, mint))
+                    .or_insert((balance.ui_token_amount.decimals, 0));
+                *delta += sign * amount as i128;
+            }
+        }
+    }
+    let tokens = token_deltas
+        .into_iter()
+        .filter(|(_, (_, delta))| *delta != 0)
+        .map(|((owner, mint), (decimals, delta))| {
+            TokenBalanceChange::new(owner, mint, decimals, delta)
+        })
+        .collect();
+
+    BalanceChanges { sol, tokens }
+}
+
+fn parse_token_balance(balance: &TransactionTokenBalance) -> Option<(Pubkey, Pubkey, u64)> {
+    Some((
+        balance.owner.parse().ok()?,
+        balance.mint.parse().ok()?,
+        balance.ui_token_amount.amount.parse().ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_account_decoder::parse_token::UiTokenAmount;
+
+    fn token_balance(
+        account_index: u8,
+        owner: &Pubkey,
+        mint: &Pubkey,
+        amount: u64,
+    ) -> TransactionTokenBalance {
+        TransactionTokenBalance {
+            account_index,
+            mint: mint.to_string(),
+            ui_token_amount: UiTokenAmount {
+                ui_amount: None,
+                decimals: 2,
+                amount: amount.to_string(),
+                ui_amount_string: String::new(),
+            },
+            owner: owner.to_string(),
+            program_id: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_sol_changes_skip_unchanged_accounts() {
+        let keys = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let changes = build_balance_changes(&keys, &[10, 5, 0], &[4, 5, 6], &[], &[]);
+        let sol: Vec<(Pubkey, i64)> = changes.sol.iter().map(|s| (s.pubkey, s.delta)).collect();
+        assert_eq!(sol, vec![(keys[0], -6), (keys[2], 6)]);
+        assert!(changes.tokens.is_empty());
+    }
+
+    #[test]
+    fn test_token_changes_are_netted_per_owner_and_mint() {
+        let (owner, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let other_owner = Pubkey::new_unique();
+        let pre = vec![
+            token_balance(1, &owner, &mint, 100),
+            token_balance(2, &owner, &mint, 50),
+            token_balance(3, &other_owner, &mint, 7),
+        ];
+        // The owner moves tokens between its own accounts and receives 25 more.
+        let post = vec![
+            token_balance(1, &owner, &mint, 0),
+            token_balance(2, &owner, &mint, 175),
+            token_balance(3, &other_owner, &mint, 7),
+        ];
+        let changes = build_balance_changes(&[], &[], &[], &pre, &post);
+        assert_eq!(changes.tokens.len(), 1);
+        let change = &changes.tokens[0];
+        assert_eq!((change.owner, change.mint), (owner, mint));
+        assert_eq!(change.amount_delta, 25);
+        assert_eq!(change.ui_amount_delta, 0.25);
+    }
+
+    #[test]
+    fn test_aggregate_nets_changes_across_transactions() {
+        let (payer, recipient) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (owner, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let first = build_balance_changes(
+            &[payer, recipient],
+            &[100, 0],
+            &[90, 10],
+            &[token_balance(0, &owner, &mint, 0)],
+            &[token_balance(0, &owner, &mint, 30)],
+        );
+        let second = build_balance_changes(
+            &[recipient, payer],
+            &[10, 90],
+            &[0, 95],
+            &[token_balance(0, &owner, &mint, 30)],
+            &[token_balance(0, &owner, &mint, 10)],
+        );
+
+        let batch = BalanceChanges::aggregate([&first, &second]);
+        assert_eq!(batch.sol_delta(&payer), -5);
+        assert_eq!(batch.sol_delta(&recipient), 0);
+        assert_eq!(batch.sol.len(), 1);
+        assert_eq!(batch.tokens.len(), 1);
+        assert_eq!(batch.tokens[0].amount_delta, 10);
+        assert_eq!(batch.tokens[0].ui_amount_delta, 0.1);
+    }
+}
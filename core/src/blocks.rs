@@ -0,0 +1,212 @@
+use crate::result::ExecutionResult;
+use executor_client::{BlockEntry, EncodedBlock};
+use solana_entry::entry::{next_versioned_entry, Entry};
+use solana_runtime::bank::Bank;
+use solana_sdk::{
+    clock::{Slot, UnixTimestamp},
+    hash::Hash,
+};
+use solana_transaction_status::{
+    BlockEncodingOptions, ConfirmedBlock, EncodeError, TransactionDetails,
+    TransactionWithStatusMeta, UiTransactionEncoding,
+};
+use std::collections::BTreeMap;
+
+/// Frozen bank recorded along with the transactions executed on it.
+pub struct Block {
+    pub slot: Slot,
+    pub block: ConfirmedBlock,
+    /// One entry per transaction followed by the ticks, chained from the previous blockhash up
+    /// to the block's one, unless the blockhash was set explicitly.
+    pub entries: Vec<Entry>,
+}
+
+impl Block {
+    pub fn encode(
+        &self,
+        encoding: UiTransactionEncoding,
+        options: BlockEncodingOptions,
+    ) -> Result<EncodedBlock, EncodeError> {
+        Ok(EncodedBlock {
+            slot: self.slot,
+            block: self.block.clone().encode_with_options(encoding, options)?,
+            entries: self
+                .entries
+                .iter()
+                .map(|entry| BlockEntry {
+                    num_hashes: entry.num_hashes,
+                    hash: entry.hash,
+                    signatures: entry
+                        .transactions
+                        .iter()
+                        .filter_map(|tx| tx.signatures.first().copied())
+                        .collect(),
+                })
+                .collect(),
+        })
+    }
+
+    /// Full transactions with their metas, as returned by the REST API.
+    pub fn default_encoding_options() -> BlockEncodingOptions {
+        BlockEncodingOptions {
+            transaction_details: TransactionDetails::Full,
+            show_rewards: false,
+            max_supported_transaction_version: None,
+        }
+    }
+}
+
+/// Blocks produced by the executor, and the transactions and entries of the block being built.
+#[derive(Default)]
+pub struct BlockStore {
+    blocks: BTreeMap<Slot, Block>,
+    pending_transactions: Vec<TransactionWithStatusMeta>,
+    pending_entries: Vec<Entry>,
+}
+
+impl BlockStore {
+    /// Records an executed transaction in an entry chained from the pending ones, or from the
+    /// previous blockhash.
+    pub fn record_transaction(&mut self, result: &ExecutionResult, previous_blockhash: &Hash) {
+        let transaction = result.to_confirmed_transaction().tx_with_meta;
+        let entry = next_versioned_entry(
+            &self.last_entry_hash(previous_blockhash),
+            1,
+            vec![transaction.get_transaction()],
+        );
+        self.pending_transactions.push(transaction);
+        self.pending_entries.push(entry);
+    }
+
+    /// Records a tick chained like the transaction entries, returning its hash for the bank to
+    /// register.
+    pub fn record_tick(&mut self, previous_blockhash: &Hash) -> Hash {
+        let entry = next_versioned_entry(&self.last_entry_hash(previous_blockhash), 1, vec![]);
+        let hash = entry.hash;
+        self.pending_entries.push(entry);
+        hash
+    }
+
+    fn last_entry_hash(&self, previous_blockhash: &Hash) -> Hash {
+        self.pending_entries
+            .last()
+            .map_or(*previous_blockhash, |entry| entry.hash)
+    }
+
+    /// Records the frozen `bank` as a block holding the pending transactions and entries.
+    pub fn close_block(&mut self, bank: &Bank, block_time: UnixTimestamp) {
+        let transactions = std::mem::take(&mut self.pending_transactions);
+        let entries = std::mem::take(&mut self.pending_entries);
+        let previous_blockhash = bank
+            .parent()
+            .map(|parent| parent.last_blockhash())
+            .unwrap_or_default();
+
+        self.blocks.insert(
+            bank.slot(),
+            Block {
+                slot: bank.slot(),
+                block: ConfirmedBlock {
+                    previous_blockhash: previous_blockhash.to_string(),
+                    blockhash: bank.last_blockhash().to_string(),
+                    parent_slot: bank.parent_slot(),
+                    transactions,
+                    rewards: vec![],
+                    block_time: Some(block_time),
+                    block_height: Some(bank.block_height()),
+                },
+                entries,
+            },
+        );
+    }
+
+    /// Drops the pending transactions and entries of a bank that won't be recorded.
+    pub fn discard_pending_transactions(&mut self) {
+        self.pending_transactions.clear();
+        self.pending_entries.clear();
+    }
+
+    pub fn get_block(&self, slot: Slot) -> Option<&Block> {
+        self.blocks.get(&slot)
+    }
+
+    /// Slots of the blocks from `start_slot` to `end_slot`, both included.
+    pub fn get_blocks(&self, start_slot: Slot, end_slot: Option<Slot>) -> Vec<Slot> {
+        let end_slot = end_slot.unwrap_or(Slot::MAX);
+        if end_slot < start_slot {
+            return vec![];
+        }
+        self.blocks
+            .range(start_slot..=end_slot)
+            .map(|(slot, _)| *slot)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_runtime::genesis_utils::create_genesis_config;
+    use solana_sdk::{pubkey::Pubkey, signature::Keypair, system_transaction};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_close_block_chains_entries_to_the_blockhash() {
+        let parent = Arc::new(Bank::new_for_tests(
+            &create_genesis_config(1_000_000).genesis_config,
+        ));
+        let bank = Bank::new_from_parent(&parent, &Pubkey::default(), 1);
+        let previous_blockhash = parent.last_blockhash();
+        let mut blocks = BlockStore::default();
+
+        let transaction = system_transaction::transfer(
+            &Keypair::new(),
+            &Pubkey::new_unique(),
+            1,
+            previous_blockhash,
+        );
+        blocks.record_transaction(
+            &ExecutionResult::for_tests(transaction.into(), 1),
+            &previous_blockhash,
+        );
+        while bank.last_blockhash() == previous_blockhash {
+            bank.register_tick(&blocks.record_tick(&previous_blockhash));
+        }
+        bank.freeze();
+        blocks.close_block(&bank, 0);
+
+        let block = blocks.get_block(1).unwrap();
+        assert_eq!(block.block.transactions.len(), 1);
+        assert_eq!(block.entries[0].transactions.len(), 1);
+        assert!(block.entries[1..]
+            .iter()
+            .all(|entry| entry.transactions.is_empty()));
+        let mut hash = previous_blockhash;
+        for entry in &block.entries {
+            assert!(entry.verify(&hash));
+            hash = entry.hash;
+        }
+        assert_eq!(hash, bank.last_blockhash());
+        assert_eq!(block.block.blockhash, bank.last_blockhash().to_string());
+        assert_eq!(
+            block.block.previous_blockhash,
+            previous_blockhash.to_string()
+        );
+        assert_eq!(blocks.get_blocks(0, None), vec![1]);
+    }
+
+    #[test]
+    fn test_discarded_entries_are_not_recorded() {
+        let bank = Bank::new_for_tests(&create_genesis_config(1_000_000).genesis_config);
+        let mut blocks = BlockStore::default();
+
+        blocks.record_tick(&bank.last_blockhash());
+        blocks.discard_pending_transactions();
+        let first_tick = blocks.record_tick(&bank.last_blockhash());
+
+        assert_eq!(
+            first_tick,
+            next_versioned_entry(&bank.last_blockhash(), 1, vec![]).hash
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use crate::result::SimulationResult;
+use executor_client::{BalanceChanges, BundleRules, BundleViolation};
+use itertools::Itertools;
+use solana_sdk::transaction::Transaction;
+
+/// Checks the bundle and its simulation results against the rules, returning the lamports the
+/// tip accounts received along with the broken rules. The tip is zero once a transaction fails,
+/// the bundle not landing.
+pub fn validate_bundle(
+    bundle: &[Transaction],
+    results: &[SimulationResult],
+    rules: &BundleRules,
+) -> (u64, Vec<BundleViolation>) {
+    let mut violations = vec![];
+
+    if bundle.len() > rules.max_transactions {
+        violations.push(BundleViolation::TooManyTransactions {
+            count: bundle.len(),
+            max: rules.max_transactions,
+        });
+    }
+
+    violations.extend(
+        bundle
+            .iter()
+            .flat_map(|tx| tx.signatures.iter())
+            .duplicates()
+            .map(|signature| BundleViolation::DuplicateSignature(*signature)),
+    );
+
+    let failures: Vec<BundleViolation> = results
+        .iter()
+        .enumerate()
+        .filter_map(|(index, result)| {
+            result
+                .result
+                .status
+                .clone()
+                .err()
+                .map(|error| BundleViolation::TransactionFailed { index, error })
+        })
+        .collect();
+    // A failed bundle doesn't land, so its tip is never paid
+    if !failures.is_empty() {
+        violations.extend(failures);
+        return (0, violations);
+    }
+
+    let balance_changes =
+        BalanceChanges::aggregate(results.iter().map(|r| &r.result.balance_changes));
+    let tip_lamports = rules
+        .tip_accounts
+        .iter()
+        .unique()
+        .map(|tip_account| balance_changes.sol_delta(tip_account).max(0) as u64)
+        .sum();
+    if tip_lamports < rules.min_tip_lamports {
+        violations.push(BundleViolation::InsufficientTip {
+            tip_lamports,
+            min_tip_lamports: rules.min_tip_lamports,
+        });
+    }
+
+    (tip_lamports, violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::result::ExecutionResult;
+    use executor_client::SolBalanceChange;
+    use solana_sdk::{
+        hash::Hash,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        system_instruction,
+        transaction::{TransactionError, VersionedTransaction},
+    };
+
+    fn transaction() -> Transaction {
+        let payer = Keypair::new();
+        Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(
+                &payer.pubkey(),
+                &Pubkey::new_unique(),
+                1,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::default(),
+        )
+    }
+
+    fn simulation(tx: &Transaction, tips: &[(Pubkey, i64)]) -> SimulationResult {
+        let mut result = ExecutionResult::for_tests(VersionedTransaction::from(tx.clone()), 0);
+        result.balance_changes.sol = tips
+            .iter()
+            .map(|(pubkey, delta)| SolBalanceChange {
+                pubkey: *pubkey,
+                delta: *delta,
+            })
+            .collect();
+        SimulationResult {
+            result,
+            post_accounts: vec![],
+        }
+    }
+
+    fn rules(tip_accounts: Vec<Pubkey>, min_tip_lamports: u64) -> BundleRules {
+        BundleRules {
+            max_transactions: 5,
+            tip_accounts,
+            min_tip_lamports,
+        }
+    }
+
+    #[test]
+    fn test_duplicate_signatures() {
+        let tx = transaction();
+        let bundle = vec![tx.clone(), tx.clone()];
+        let results = vec![simulation(&tx, &[]), simulation(&tx, &[])];
+        let (_, violations) = validate_bundle(&bundle, &results, &rules(vec![], 0));
+        assert_eq!(violations.len(), 1);
+        assert!(matches!(
+            violations[0],
+            BundleViolation::DuplicateSignature(signature) if signature == tx.signatures[0]
+        ));
+    }
+
+    #[test]
+    fn test_failed_bundle_pays_no_tip() {
+        let tip_account = Pubkey::new_unique();
+        let bundle = vec![transaction(), transaction()];
+        let mut failed = simulation(&bundle[1], &[]);
+        failed.result.status = Err(TransactionError::AccountNotFound);
+        let results = vec![simulation(&bundle[0], &[(tip_account, 10_000)]), failed];
+
+        let (tip_lamports, violations) =
+            validate_bundle(&bundle, &results, &rules(vec![tip_account], 1000));
+        assert_eq!(tip_lamports, 0);
+        assert_eq!(violations.len(), 1);
+        assert!(matches!(
+            violations[0],
+            BundleViolation::TransactionFailed { index: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_tip_accounts_are_counted_once() {
+        let (first_tip, second_tip) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let bundle = vec![transaction(), transaction()];
+        let results = vec![
+            simulation(&bundle[0], &[(first_tip, 600)]),
+            simulation(&bundle[1], &[(first_tip, 400), (second_tip, 100)]),
+        ];
+        let tip_accounts = vec![first_tip, second_tip, first_tip];
+
+        let (tip_lamports, violations) =
+            validate_bundle(&bundle, &results, &rules(tip_accounts.clone(), 1100));
+        assert_eq!(tip_lamports, 1100);
+        assert!(violations.is_empty());
+
+        let (tip_lamports, violations) =
+            validate_bundle(&bundle, &results, &rules(tip_accounts, 2000));
+        assert_eq!(tip_lamports, 1100);
+        assert!(matches!(
+            violations[..],
+            [BundleViolation::InsufficientTip {
+                tip_lamports: 1100,
+                min_tip_lamports: 2000
+            }]
+        ));
+    }
+
+    #[test]
+    fn test_too_many_transactions() {
+        let bundle: Vec<Transaction> = (0..6).map(|_| transaction()).collect();
+        let results: Vec<SimulationResult> = bundle.iter().map(|tx| simulation(tx, &[])).collect();
+        let (_, violations) = validate_bundle(&bundle, &results, &rules(vec![], 0));
+        assert!(matches!(
+            violations[..],
+            [BundleViolation::TooManyTransactions { count: 6, max: 5 }]
+        ));
+    }
+}
@@ -0,0 +1,229 @@
+use executor_client::Invocation;
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+use solana_transaction_status::InnerInstructions;
+
+/// Rebuilds the tree of program invocations from the log collector output, pairing each
+/// invocation with its top-level or inner instruction record.
+pub fn build_call_tree(
+    account_keys: &[Pubkey],
+    instructions: &[CompiledInstruction],
+    inner_instructions: &[InnerInstructions],
+    log_messages: &[String],
+) -> Vec<Invocation> {
+    let no_inner_instructions: &[CompiledInstruction] = &[];
+    let mut roots = vec![];
+    let mut stack: Vec<Invocation> = vec![];
+    let mut next_instruction_index = 0;
+    let mut pending_inner_instructions = no_inner_instructions.iter();
+
+    for line in log_messages {
+        if let Some((program_id, stack_height)) = parse_invoke(line) {
+            let instruction = if stack_height == 1 {
+                // Precompiles don't log their invocation, skip over them
+                let index = instructions[next_instruction_index..]
+                    .iter()
+                    .position(|ix| account_keys[ix.program_id_index as usize] == program_id)
+                    .map(|offset| next_instruction_index + offset);
+                index.and_then(|index| {
+                    next_instruction_index = index + 1;
+                    pending_inner_instructions = inner_instructions
+                        .iter()
+                        .find(|inner| inner.index as usize == index)
+                        .map_or_else(
+                            || no_inner_instructions.iter(),
+                            |inner| inner.instructions.iter(),
+                        );
+                    instructions.get(index)
+                })
+            } else {
+                pending_inner_instructions
+                    .find(|ix| account_keys[ix.program_id_index as usize] == program_id)
+            };
+
+            stack.push(Invocation {
+                program_id,
+                data: instruction.map(|ix| ix.data.clone()).unwrap_or_default(),
+                accounts: instruction
+                    .map(|ix| {
+                        ix.accounts
+                            .iter()
+                            .map(|index| account_keys[*index as usize])
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                stack_height,
+                compute_units_consumed: None,
+                logs: vec![],
+                error: None,
+                decoded_instruction: None,
+                events: vec![],
+                inner_invocations: vec![],
+            });
+        } else if let Some((program_id, units)) = parse_consumed(line) {
+            if let Some(invocation) = stack.last_mut() {
+                if invocation.program_id == program_id {
+                    invocation.compute_units_consumed = Some(units);
+                }
+            }
+        } else if let Some((program_id, error)) = parse_completion(line) {
+            match stack.last() {
+                Some(invocation) if invocation.program_id == program_id => {
+                    let mut invocation = stack.pop().unwrap();
+                    invocation.error = error;
+                    attach(&mut stack, &mut roots, invocation);
+                }
+                _ => push_log(&mut stack, line),
+            }
+        } else {
+            push_log(&mut stack, line);
+        }
+    }
+
+    // Logs may have been truncated, close invocations which never completed
+    while let Some(invocation) = stack.pop() {
+        attach(&mut stack, &mut roots, invocation);
+    }
+
+    roots
+}
+
+fn attach(stack: &mut [Invocation], roots: &mut Vec<Invocation>, invocation: Invocation) {
+    match stack.last_mut() {
+        Some(parent) => parent.inner_invocations.push(invocation),
+        None => roots.push(invocation),
+    }
+}
+
+fn push_log(stack: &mut [Invocation], line: &str) {
+    if let Some(invocation) = stack.last_mut() {
+        invocation.logs.push(line.to_string());
+    }
+}
+
+/// Parses `Program <id> invoke [<height>]`.
+fn parse_invoke(line: &str) -> Option<(Pubkey, usize)> {
+    let (program_id, rest) = line.strip_prefix("Program ")?.split_once(" invoke [")?;
+    let stack_height = rest.strip_suffix(']')?.parse().ok()?;
+    Some((program_id.parse().ok()?, stack_height))
+}
+
+/// Parses `Program <id> consumed <units> of <limit> compute units`.
+fn parse_consumed(line: &str) -> Option<(Pubkey, u64)> {
+    let (program_id, rest) = line.strip_prefix("Program ")?.split_once(" consumed ")?;
+    let (units, _) = rest.split_once(" of ")?;
+    Some((program_id.parse().ok()?, units.parse().ok()?))
+}
+
+/// Parses `Program <id> success` and `Program <id> failed: <error>`.
+fn parse_completion(line: &str) -> Option<(Pubkey, Option<String>)> {
+    let rest = line.strip_prefix("Program ")?;
+    if let Some(program_id) = rest.strip_suffix(" success") {
+        return Some((program_id.parse().ok()?, None));
+    }
+    let (program_id, error) = rest.split_once(" failed: ")?;
+    Some((program_id.parse().ok()?, Some(error.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compiled_instruction(program_id_index: u8, data: Vec<u8>) -> CompiledInstruction {
+        CompiledInstruction {
+            program_id_index,
+            accounts: vec![0],
+            data,
+        }
+    }
+
+    #[test]
+    fn test_build_call_tree_nests_inner_invocations() {
+        let program = Pubkey::new_unique();
+        let callee = Pubkey::new_unique();
+        let account_keys = vec![Pubkey::new_unique(), program, callee];
+        let instructions = vec![compiled_instruction(1, vec![1])];
+        let inner_instructions = vec![InnerInstructions {
+            index: 0,
+            instructions: vec![compiled_instruction(2, vec![2])],
+        }];
+        let log_messages = vec![
+            format!("Program {} invoke [1]", program),
+            "Program log: outer".to_string(),
+            format!("Program {} invoke [2]", callee),
+            "Program log: inner".to_string(),
+            format!("Program {} consumed 100 of 199000 compute units", callee),
+            format!("Program {} success", callee),
+            format!("Program {} consumed 1000 of 200000 compute units", program),
+            format!("Program {} success", program),
+        ];
+
+        let roots = build_call_tree(
+            &account_keys,
+            &instructions,
+            &inner_instructions,
+            &log_messages,
+        );
+
+        assert_eq!(roots.len(), 1);
+        let root = &roots[0];
+        assert_eq!(root.program_id, program);
+        assert_eq!(root.data, vec![1]);
+        assert_eq!(root.accounts, vec![account_keys[0]]);
+        assert_eq!(root.compute_units_consumed, Some(1000));
+        assert_eq!(root.logs, vec!["Program log: outer".to_string()]);
+        assert_eq!(root.inner_invocations.len(), 1);
+        let inner = &root.inner_invocations[0];
+        assert_eq!(inner.program_id, callee);
+        assert_eq!(inner.stack_height, 2);
+        assert_eq!(inner.data, vec![2]);
+        assert_eq!(inner.compute_units_consumed, Some(100));
+        assert_eq!(inner.logs, vec!["Program log: inner".to_string()]);
+    }
+
+    #[test]
+    fn test_build_call_tree_records_failures_and_truncated_logs() {
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+        let account_keys = vec![Pubkey::new_unique(), first, second];
+        let instructions = vec![
+            compiled_instruction(1, vec![1]),
+            compiled_instruction(2, vec![2]),
+        ];
+        let log_messages = vec![
+            format!("Program {} invoke [1]", first),
+            format!("Program {} failed: custom program error: 0x1", first),
+            format!("Program {} invoke [1]", second),
+            "Log truncated".to_string(),
+        ];
+
+        let roots = build_call_tree(&account_keys, &instructions, &[], &log_messages);
+
+        assert_eq!(roots.len(), 2);
+        assert_eq!(roots[0].error.as_deref(), Some("custom program error: 0x1"));
+        assert_eq!(roots[1].program_id, second);
+        assert_eq!(roots[1].data, vec![2]);
+        assert_eq!(roots[1].error, None);
+        assert_eq!(roots[1].logs, vec!["Log truncated".to_string()]);
+    }
+
+    #[test]
+    fn test_build_call_tree_skips_precompiles() {
+        let precompile = Pubkey::new_unique();
+        let program = Pubkey::new_unique();
+        let account_keys = vec![Pubkey::new_unique(), precompile, program];
+        let instructions = vec![
+            compiled_instruction(1, vec![1]),
+            compiled_instruction(2, vec![2]),
+        ];
+        let log_messages = vec![
+            format!("Program {} invoke [1]", program),
+            format!("Program {} success", program),
+        ];
+
+        let roots = build_call_tree(&account_keys, &instructions, &[], &log_messages);
+
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].program_id, program);
+        assert_eq!(roots[0].data, vec![2]);
+    }
+}
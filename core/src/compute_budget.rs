@@ -0,0 +1,271 @@
+use executor_client::ComputeBudgetOverrides;
+use solana_program_runtime::{
+    compute_budget::{
+        ComputeBudget, DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT, MAX_COMPUTE_UNIT_LIMIT,
+    },
+    prioritization_fee::{PrioritizationFeeDetails, PrioritizationFeeType},
+};
+use solana_sdk::{
+    borsh::try_from_slice_unchecked,
+    compute_budget::{self, ComputeBudgetInstruction},
+    entrypoint::HEAP_LENGTH as MIN_HEAP_FRAME_BYTES,
+    message::Message,
+    transaction,
+};
+
+// Private in the runtime
+pub const MAX_HEAP_FRAME_BYTES: u32 = 256 * 1024;
+
+/// Compute budget requested by a transaction through its ComputeBudget instructions.
+#[derive(Default)]
+struct ComputeBudgetRequest {
+    compute_unit_limit: Option<u32>,
+    heap_frame_bytes: Option<u32>,
+    prioritization_fee_type: Option<PrioritizationFeeType>,
+    num_non_compute_budget_instructions: u32,
+}
+
+impl ComputeBudgetRequest {
+    /// Reads the requests of a message whose ComputeBudget instructions are valid.
+    fn from_message(message: &Message) -> Self {
+        let mut request = ComputeBudgetRequest::default();
+        for instruction in &message.instructions {
+            let program_id = &message.account_keys[instruction.program_id_index as usize];
+            if !compute_budget::check_id(program_id) {
+                request.num_non_compute_budget_instructions += 1;
+                continue;
+            }
+            match try_from_slice_unchecked(&instruction.data) {
+                Ok(ComputeBudgetInstruction::SetComputeUnitLimit(units)) => {
+                    request.compute_unit_limit = Some(units)
+                }
+                Ok(ComputeBudgetInstruction::RequestUnitsDeprecated {
+                    units,
+                    additional_fee,
+                }) => {
+                    request.compute_unit_limit = Some(units);
+                    request.prioritization_fee_type =
+                        Some(PrioritizationFeeType::Deprecated(additional_fee as u64));
+                }
+                Ok(ComputeBudgetInstruction::RequestHeapFrame(bytes)) => {
+                    request.heap_frame_bytes = Some(bytes)
+                }
+                Ok(ComputeBudgetInstruction::SetComputeUnitPrice(micro_lamports)) => {
+                    request.prioritization_fee_type =
+                        Some(PrioritizationFeeType::ComputeUnitPrice(micro_lamports))
+                }
+                _ => {}
+            }
+        }
+
+        request
+    }
+}
+
+/// Whether the runtime grants a heap of this size: a multiple of 1KiB between 32KiB and 256KiB.
+pub fn is_valid_heap_frame_bytes(bytes: u32) -> bool {
+    (MIN_HEAP_FRAME_BYTES as u32..=MAX_HEAP_FRAME_BYTES).contains(&bytes) && bytes % 1024 == 0
+}
+
+/// Derives the compute budget of a transaction the way the bank does, with the overrides applied.
+/// Like the bank, rejects invalid and duplicate ComputeBudget instructions and requested heap
+/// sizes the runtime doesn't grant, whatever the overrides.
+pub fn compute_budget_with_overrides(
+    message: &Message,
+    overrides: &ComputeBudgetOverrides,
+) -> transaction::Result<ComputeBudget> {
+    let mut compute_budget = ComputeBudget::new(MAX_COMPUTE_UNIT_LIMIT as u64);
+    // Both features are active on every cluster
+    compute_budget.process_instructions(
+        message.instructions.iter().map(|instruction| {
+            (
+                &message.account_keys[instruction.program_id_index as usize],
+                instruction,
+            )
+        }),
+        true,
+        true,
+    )?;
+
+    let request = match overrides.ignore_compute_budget_instructions {
+        true => ComputeBudgetRequest {
+            num_non_compute_budget_instructions: ComputeBudgetRequest::from_message(message)
+                .num_non_compute_budget_instructions,
+            ..ComputeBudgetRequest::default()
+        },
+        false => ComputeBudgetRequest::from_message(message),
+    };
+
+    let max_compute_unit_limit = overrides
+        .max_compute_unit_limit
+        .unwrap_or(MAX_COMPUTE_UNIT_LIMIT);
+    compute_budget.compute_unit_limit = overrides
+        .compute_unit_limit
+        .or(request.compute_unit_limit)
+        .unwrap_or_else(|| {
+            request.num_non_compute_budget_instructions.saturating_mul(
+                overrides
+                    .default_units_per_instruction
+                    .unwrap_or(DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT),
+            )
+        })
+        .min(max_compute_unit_limit) as u64;
+    compute_budget.heap_size = overrides
+        .heap_frame_bytes
+        .or(request.heap_frame_bytes)
+        .map(|bytes| bytes as usize);
+
+    Ok(compute_budget)
+}
+
+/// Prioritization fee a leader charges for the transaction, along with its compute unit price
+/// in micro-lamports.
+pub fn prioritization_fee(message: &Message, compute_unit_limit: u64) -> (u64, u64) {
+    match ComputeBudgetRequest::from_message(message).prioritization_fee_type {
+        Some(fee_type) => {
+            let details = PrioritizationFeeDetails::new(fee_type, compute_unit_limit);
+            (details.get_fee(), details.get_priority())
+        }
+        None => (0, 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+        system_instruction,
+        transaction::TransactionError,
+    };
+
+    fn message(instructions: &[Instruction]) -> Message {
+        Message::new(instructions, Some(&Pubkey::new_unique()))
+    }
+
+    fn transfer() -> Instruction {
+        system_instruction::transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1)
+    }
+
+    #[test]
+    fn test_compute_budget_with_overrides() {
+        let message = message(&[
+            ComputeBudgetInstruction::set_compute_unit_limit(50_000),
+            ComputeBudgetInstruction::request_heap_frame(64 * 1024),
+            transfer(),
+            transfer(),
+        ]);
+
+        let requested = compute_budget_with_overrides(&message, &Default::default()).unwrap();
+        assert_eq!(requested.compute_unit_limit, 50_000);
+        assert_eq!(requested.heap_size, Some(64 * 1024));
+
+        let overridden = compute_budget_with_overrides(
+            &message,
+            &ComputeBudgetOverrides {
+                compute_unit_limit: Some(2_000_000),
+                max_compute_unit_limit: Some(1_000_000),
+                heap_frame_bytes: Some(128 * 1024),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(overridden.compute_unit_limit, 1_000_000);
+        assert_eq!(overridden.heap_size, Some(128 * 1024));
+
+        let ignored = compute_budget_with_overrides(
+            &message,
+            &ComputeBudgetOverrides {
+                default_units_per_instruction: Some(1_000),
+                ignore_compute_budget_instructions: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(ignored.compute_unit_limit, 2_000);
+        assert_eq!(ignored.heap_size, None);
+    }
+
+    #[test]
+    fn test_compute_budget_rejects_duplicate_instructions() {
+        let message = message(&[
+            ComputeBudgetInstruction::set_compute_unit_limit(50_000),
+            transfer(),
+            ComputeBudgetInstruction::set_compute_unit_limit(60_000),
+        ]);
+
+        for overrides in [
+            ComputeBudgetOverrides::default(),
+            ComputeBudgetOverrides {
+                compute_unit_limit: Some(100_000),
+                ignore_compute_budget_instructions: true,
+                ..Default::default()
+            },
+        ] {
+            assert_eq!(
+                compute_budget_with_overrides(&message, &overrides).err(),
+                Some(TransactionError::DuplicateInstruction(2))
+            );
+        }
+    }
+
+    #[test]
+    fn test_compute_budget_rejects_invalid_instruction_data() {
+        let message = message(&[
+            transfer(),
+            Instruction::new_with_bytes(compute_budget::id(), &[0xff], vec![]),
+        ]);
+
+        assert_eq!(
+            compute_budget_with_overrides(&message, &Default::default()).err(),
+            Some(TransactionError::InstructionError(
+                1,
+                InstructionError::InvalidInstructionData
+            ))
+        );
+    }
+
+    #[test]
+    fn test_compute_budget_rejects_invalid_heap_frames() {
+        for bytes in [
+            MIN_HEAP_FRAME_BYTES as u32 - 1024,
+            MAX_HEAP_FRAME_BYTES + 1024,
+            64 * 1024 + 1,
+        ] {
+            let message = message(&[
+                transfer(),
+                ComputeBudgetInstruction::request_heap_frame(bytes),
+            ]);
+            let overrides = ComputeBudgetOverrides {
+                heap_frame_bytes: Some(64 * 1024),
+                ..Default::default()
+            };
+
+            assert!(!is_valid_heap_frame_bytes(bytes));
+            assert_eq!(
+                compute_budget_with_overrides(&message, &overrides).err(),
+                Some(TransactionError::InstructionError(
+                    1,
+                    InstructionError::InvalidInstructionData
+                ))
+            );
+        }
+        assert!(is_valid_heap_frame_bytes(MIN_HEAP_FRAME_BYTES as u32));
+        assert!(is_valid_heap_frame_bytes(MAX_HEAP_FRAME_BYTES));
+    }
+
+    #[test]
+    fn test_prioritization_fee() {
+        let priced_message = message(&[
+            ComputeBudgetInstruction::set_compute_unit_price(1_500_000),
+            transfer(),
+        ]);
+
+        assert_eq!(
+            prioritization_fee(&priced_message, 200_000),
+            (300_000, 1_500_000)
+        );
+        assert_eq!(prioritization_fee(&message(&[transfer()]), 200_000), (0, 0));
+    }
+}
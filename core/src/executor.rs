@@ -1,12 +1,46 @@
 use crate::{
+    account_diff::diff_account,
+    account_graph::build_account_graph,
+    account_parser::{parse_builtin_account, AccountParser},
+    balances::build_balance_changes,
+    blocks::{Block, BlockStore},
+    bundle::validate_bundle,
+    call_tree::build_call_tree,
+    compute_budget::{
+        compute_budget_with_overrides, is_valid_heap_frame_bytes, MAX_HEAP_FRAME_BYTES,
+    },
+    fees::build_fee_breakdown,
+    history::TransactionHistory,
+    idl::{Idl, IdlRegistry},
+    ordering_search::{best_outcome, ordering_outcome, search_orderings},
+    profiling::build_profile,
+    program_errors::ProgramErrorRegistry,
     programs::{
         self, BPF_LOADER2_PID, BPF_LOADER_UPGRADEABLE_PID, SPL_ASSOCIATED_TOKEN_PID, SPL_MEMO1_PID,
-        SPL_TOKEN_PID, SYSTEM_PID, SYSVAR_PID, SYSVAR_RENT_ADDRESS,
+        SPL_MEMO3_PID, SPL_TOKEN_PID, SYSTEM_PID, SYSVAR_PID, SYSVAR_RENT_ADDRESS,
+    },
+    result::{
+        BundleSimulationResult, ExecutionResult, ScheduleSimulationResult, SealedBlock,
+        SimulationResult,
+    },
+    scheduling::analyze_schedule,
+    spl_token::{
+        delegate_filter_bytes, unpack_token_account, MINT_DECIMALS_OFFSET,
+        TOKEN_ACCOUNT_DELEGATE_OFFSET, TOKEN_ACCOUNT_LEN, TOKEN_ACCOUNT_MINT_OFFSET,
     },
-    utils::{clone_keypair, random_keypair},
+    transaction_queue::TransactionQueue,
+    utils::{clone_keypair, random_keypair, unix_timestamp_now},
+};
+use anyhow::{anyhow, bail};
+use executor_client::{
+    AccountFilter, AccountGraph, AccountOverride, AccountPatch, BlockProduction, BundleRules,
+    ComputeBudgetOverrides, ComputeUnitsEstimate, OrderingPolicy, OrderingSearchRequest,
+    OrderingSearchResult, ParsedAccount, ParsedAccountInfo, ProgramErrorDefinition,
+    ScheduleAnalysis, ScheduleComparison, SearchStrategy, SignatureInfo, TokenAccountBalance,
+    DEFAULT_RPC_ENDPOINT, MAX_EXHAUSTIVE_SEARCH_CANDIDATES,
 };
-use executor_client::DEFAULT_RPC_ENDPOINT;
 use itertools::{izip, Itertools};
+use solana_account_decoder::parse_token::{get_token_account_mint, is_known_spl_token_id};
 use solana_bpf_loader_program::{
     solana_bpf_loader_deprecated_program, solana_bpf_loader_program,
     solana_bpf_loader_upgradeable_program,
@@ -14,45 +48,61 @@ use solana_bpf_loader_program::{
 use solana_client::{client_error::reqwest::Url, rpc_client::RpcClient};
 use solana_ledger::token_balances;
 use solana_program::native_token::LAMPORTS_PER_SOL;
+use solana_program_runtime::{compute_budget::MAX_COMPUTE_UNIT_LIMIT, timings::ExecuteTimings};
 use solana_runtime::{
     accounts_db::AccountShrinkThreshold,
-    accounts_index::AccountSecondaryIndexes,
+    accounts_index::{AccountIndex, AccountSecondaryIndexes, IndexKey, ScanConfig},
     bank::{
-        Bank, TransactionBalancesSet, TransactionExecutionDetails, TransactionExecutionResult,
-        TransactionResults,
+        Bank, RentDebits, TransactionBalancesSet, TransactionExecutionDetails,
+        TransactionExecutionResult, TransactionResults,
     },
     builtins::{Builtin, Builtins},
     genesis_utils,
 };
 use solana_sdk::{
     account::Account,
-    account::AccountSharedData,
-    clock::UnixTimestamp,
+    account::{AccountSharedData, ReadableAccount},
+    clock::{Slot, UnixTimestamp},
     commitment_config::{CommitmentConfig, CommitmentLevel},
+    entrypoint::HEAP_LENGTH as MIN_HEAP_FRAME_BYTES,
     feature_set,
     genesis_config::GenesisConfig,
     hash::Hash,
-    message::{v0::LoadedAddresses, SanitizedMessage},
+    message::SanitizedMessage,
     packet,
     pubkey::Pubkey,
-    signature::{Keypair, Signer},
+    signature::{Keypair, Signature, Signer},
+    system_instruction::MAX_PERMITTED_DATA_LENGTH,
     transaction::{Transaction, VersionedTransaction},
 };
 use solana_transaction_status::{
-    ConfirmedTransactionWithStatusMeta, EncodedConfirmedTransactionWithStatusMeta,
-    InnerInstructions, TransactionStatusMeta, TransactionTokenBalance, TransactionWithStatusMeta,
-    UiTransactionEncoding, VersionedTransactionWithStatusMeta,
+    ConfirmedTransactionWithStatusMeta, InnerInstructions, TransactionStatus,
+    TransactionTokenBalance,
 };
 use std::{
+    cmp::Ordering,
     collections::{HashMap, HashSet},
+    fmt::{Debug, Display},
     path::Path,
+    sync::Arc,
     time::{SystemTime, UNIX_EPOCH},
 };
 
 pub struct Executor {
-    bank: Bank,
+    bank: Arc<Bank>,
     faucet: Keypair,
     rpc_client: RpcClient,
+    last_slot: Slot,
+    /// Blockhashes registered on the working bank, replayed on its forks.
+    registered_blockhashes: Vec<Hash>,
+    compute_budget_overrides: Option<ComputeBudgetOverrides>,
+    program_errors: ProgramErrorRegistry,
+    idls: IdlRegistry,
+    account_parsers: HashMap<Pubkey, Vec<Box<dyn AccountParser>>>,
+    history: TransactionHistory,
+    block_production: BlockProduction,
+    blocks: BlockStore,
+    transaction_queue: TransactionQueue,
 }
 
 impl Executor {
@@ -69,7 +119,7 @@ impl Executor {
     }
 
     pub fn bank_mut(&mut self) -> &mut Bank {
-        &mut self.bank
+        Arc::get_mut(&mut self.bank).expect("Working bank must not be shared")
     }
 
     pub fn payer(&self) -> Keypair {
@@ -81,7 +131,7 @@ impl Executor {
     }
 
     pub fn get_latest_blockhash(&self) -> Hash {
-        self.bank.confirmed_last_blockhash()
+        self.bank.last_blockhash()
     }
 
     pub fn get_account(&self, pubkey: &Pubkey) -> Option<Account> {
@@ -95,6 +145,238 @@ impl Executor {
             .collect_vec()
     }
 
+    /// Accounts owned by the program which match all the filters.
+    pub fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: &[AccountFilter],
+    ) -> Vec<(Pubkey, Account)> {
+        self.bank
+            .get_filtered_indexed_accounts(
+                &IndexKey::ProgramId(*program_id),
+                // The secondary index keeps stale entries for reassigned and closed accounts
+                |account| {
+                    account.owner() == program_id
+                        && filters.iter().all(|filter| filter.matches(account.data()))
+                },
+                &ScanConfig::default(),
+                None,
+            )
+            .expect("Failed to scan program accounts")
+            .into_iter()
+            .map(|(pubkey, account)| (pubkey, account.into()))
+            .collect_vec()
+    }
+
+    /// Fails if the mint of one of the accounts isn't loaded, like RPC nodes.
+    pub fn get_token_accounts_by_owner(
+        &self,
+        owner: &Pubkey,
+        mint: Option<&Pubkey>,
+    ) -> anyhow::Result<Vec<TokenAccountBalance>> {
+        if let Some(mint) = mint {
+            self.check_mint_loaded(mint)?;
+        }
+        Ok(self
+            .get_indexed_token_accounts(&IndexKey::SplTokenOwner(*owner))?
+            .into_iter()
+            // The owner index isn't pruned when `SetAuthority` transfers an account away
+            .filter(|balance| balance.owner == *owner)
+            .filter(|balance| mint.map_or(true, |mint| balance.mint == *mint))
+            .collect_vec())
+    }
+
+    /// Fails if the mint of one of the accounts isn't loaded, like RPC nodes.
+    pub fn get_token_accounts_by_delegate(
+        &self,
+        delegate: &Pubkey,
+        mint: Option<&Pubkey>,
+    ) -> anyhow::Result<Vec<TokenAccountBalance>> {
+        if let Some(mint) = mint {
+            self.check_mint_loaded(mint)?;
+        }
+        let mut filters = vec![
+            AccountFilter::DataSize(TOKEN_ACCOUNT_LEN as u64),
+            AccountFilter::Memcmp {
+                offset: TOKEN_ACCOUNT_DELEGATE_OFFSET,
+                bytes: delegate_filter_bytes(delegate),
+            },
+        ];
+        if let Some(mint) = mint {
+            filters.push(AccountFilter::Memcmp {
+                offset: TOKEN_ACCOUNT_MINT_OFFSET,
+                bytes: mint.to_bytes().to_vec(),
+            });
+        }
+
+        self.get_program_accounts(&SPL_TOKEN_PID, &filters)
+            .iter()
+            .filter_map(|(pubkey, account)| {
+                self.token_account_balance(pubkey, &account.data)
+                    .transpose()
+            })
+            .collect()
+    }
+
+    /// Token accounts of the mint with the largest balances, in decreasing order. Fails if the
+    /// mint isn't loaded.
+    pub fn get_token_largest_accounts(
+        &self,
+        mint: &Pubkey,
+        limit: usize,
+    ) -> anyhow::Result<Vec<TokenAccountBalance>> {
+        self.check_mint_loaded(mint)?;
+        let mut balances = self.get_indexed_token_accounts(&IndexKey::SplTokenMint(*mint))?;
+        balances.sort_by(|a, b| b.amount.cmp(&a.amount));
+        balances.truncate(limit);
+        Ok(balances)
+    }
+
+    fn get_indexed_token_accounts(
+        &self,
+        index_key: &IndexKey,
+    ) -> anyhow::Result<Vec<TokenAccountBalance>> {
+        self.bank
+            .get_filtered_indexed_accounts(
+                index_key,
+                |account| account.owner() == &SPL_TOKEN_PID,
+                &ScanConfig::default(),
+                None,
+            )
+            .expect("Failed to scan token accounts")
+            .iter()
+            .filter_map(|(pubkey, account)| {
+                self.token_account_balance(pubkey, account.data())
+                    .transpose()
+            })
+            .collect()
+    }
+
+    fn check_mint_loaded(&self, mint: &Pubkey) -> anyhow::Result<()> {
+        match self.get_account(mint) {
+            Some(_) => Ok(()),
+            None => bail!("Mint {} isn't loaded", mint),
+        }
+    }
+
+    /// Balance of an initialized token account, failing if its mint isn't loaded.
+    fn token_account_balance(
+        &self,
+        pubkey: &Pubkey,
+        data: &[u8],
+    ) -> anyhow::Result<Option<TokenAccountBalance>> {
+        let (mint, owner, amount) = match unpack_token_account(data) {
+            Some(token_account) => token_account,
+            None => return Ok(None),
+        };
+        let decimals = self
+            .get_account(&mint)
+            .and_then(|mint| mint.data.get(MINT_DECIMALS_OFFSET).copied())
+            .ok_or_else(|| anyhow!("Mint {} of token account {} isn't loaded", mint, pubkey))?;
+        Ok(Some(TokenAccountBalance::new(
+            *pubkey, mint, owner, amount, decimals,
+        )))
+    }
+
+    pub fn get_parsed_account(&self, pubkey: &Pubkey) -> Option<ParsedAccountInfo> {
+        self.get_account(pubkey).map(|account| ParsedAccountInfo {
+            parsed: self.parse_account(pubkey, &account),
+            account,
+        })
+    }
+
+    pub fn get_parsed_accounts(&self, pubkeys: &[Pubkey]) -> Vec<Option<ParsedAccountInfo>> {
+        pubkeys
+            .iter()
+            .map(|pk| self.get_parsed_account(pk))
+            .collect_vec()
+    }
+
+    /// Decodes an account with the parsers registered for its owner, then its owner's IDL, then
+    /// the builtin parsers.
+    pub fn parse_account(&self, pubkey: &Pubkey, account: &Account) -> Option<ParsedAccount> {
+        if let Some(parsed) = self
+            .account_parsers
+            .get(&account.owner)
+            .into_iter()
+            .flatten()
+            .find_map(|parser| parser.parse_account(pubkey, account))
+        {
+            return Some(parsed);
+        }
+        if let Some(parsed) = self.idls.parse_account(account) {
+            return Some(parsed);
+        }
+
+        parse_builtin_account(pubkey, account, self.get_spl_token_decimals(account))
+    }
+
+    /// Decimals of the mint of an SPL Token account.
+    pub fn get_spl_token_decimals(&self, account: &Account) -> Option<u8> {
+        is_known_spl_token_id(&account.owner)
+            .then(|| get_token_account_mint(&account.data))
+            .flatten()
+            .and_then(|mint| self.get_account(&mint))
+            .and_then(|mint| mint.data.get(MINT_DECIMALS_OFFSET).copied())
+    }
+
+    /// Decodes the accounts owned by `owner` in parsed account mode, before the IDL and builtin
+    /// parsers are tried.
+    pub fn register_account_parser(&mut self, owner: Pubkey, parser: Box<dyn AccountParser>) {
+        self.account_parsers.entry(owner).or_default().push(parser);
+    }
+
+    /// Transaction executed on the working bank, with its status meta.
+    pub fn get_transaction(
+        &self,
+        signature: &Signature,
+    ) -> Option<&ConfirmedTransactionWithStatusMeta> {
+        self.history.get_transaction(signature)
+    }
+
+    pub fn get_signature_statuses(
+        &self,
+        signatures: &[Signature],
+    ) -> Vec<Option<TransactionStatus>> {
+        signatures
+            .iter()
+            .map(|signature| self.history.get_signature_status(signature))
+            .collect()
+    }
+
+    pub fn get_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before: Option<&Signature>,
+        until: Option<&Signature>,
+        limit: usize,
+    ) -> Vec<SignatureInfo> {
+        self.history
+            .get_signatures_for_address(address, before, until, limit)
+    }
+
+    /// Block produced at `slot`, if any.
+    pub fn get_block(&self, slot: Slot) -> Option<&Block> {
+        self.blocks.get_block(slot)
+    }
+
+    /// Slots of the produced blocks from `start_slot` to `end_slot`, both included.
+    pub fn get_blocks(&self, start_slot: Slot, end_slot: Option<Slot>) -> Vec<Slot> {
+        self.blocks.get_blocks(start_slot, end_slot)
+    }
+
+    pub fn set_block_production(&mut self, block_production: BlockProduction) {
+        self.block_production = block_production;
+    }
+
+    /// Ends the working bank's block: advances its blockhash, freezes it and records it unless
+    /// block production is disabled, then continues on a child bank. Returns the slot of the
+    /// closed block.
+    pub fn close_block(&mut self) -> Slot {
+        self.register_blockhash(None);
+        self.freeze_working_bank(true).slot()
+    }
+
     pub fn set_rpc_config(&mut self, rpc_endpoint: String, commitment_level: CommitmentLevel) {
         self.rpc_client = RpcClient::new_with_commitment(
             rpc_endpoint,
@@ -104,7 +386,88 @@ impl Executor {
         );
     }
 
-    pub fn advance_blockhash(&self, hash: Option<Hash>) -> Hash {
+    /// Overrides the compute budget of subsequent executions, `None` restores the bank's behavior.
+    pub fn set_compute_budget_overrides(
+        &mut self,
+        compute_budget_overrides: Option<ComputeBudgetOverrides>,
+    ) -> anyhow::Result<()> {
+        let heap_frame_bytes =
+            compute_budget_overrides.and_then(|overrides| overrides.heap_frame_bytes);
+        if let Some(bytes) = heap_frame_bytes.filter(|bytes| !is_valid_heap_frame_bytes(*bytes)) {
+            bail!(
+                "Heap frame of {} bytes isn't a multiple of 1024 between {} and {}",
+                bytes,
+                MIN_HEAP_FRAME_BYTES,
+                MAX_HEAP_FRAME_BYTES
+            );
+        }
+        self.compute_budget_overrides = compute_budget_overrides;
+        Ok(())
+    }
+
+    /// Names the custom error codes of a program in subsequent results.
+    pub fn register_program_errors(
+        &mut self,
+        program_id: Pubkey,
+        errors: Vec<ProgramErrorDefinition>,
+    ) {
+        self.program_errors.register_errors(program_id, errors);
+    }
+
+    /// Names the custom error codes of a program in subsequent results, from its error enum.
+    pub fn register_program_error_enum<E: Debug + Display + 'static>(
+        &mut self,
+        program_id: Pubkey,
+        from_code: fn(u32) -> Option<E>,
+    ) {
+        self.program_errors
+            .register_error_enum(program_id, from_code);
+    }
+
+    /// Names the Anchor framework error codes of a program in subsequent results, for programs
+    /// without a registered IDL.
+    pub fn register_anchor_program(&mut self, program_id: Pubkey) {
+        self.program_errors.register_anchor_program(program_id);
+    }
+
+    /// Decodes the instructions and events of a program in subsequent results, along with the
+    /// errors the IDL declares and the Anchor framework errors. Errors registered through
+    /// [`Self::register_program_errors`] keep precedence over the IDL ones.
+    pub fn register_idl(&mut self, program_id: Pubkey, idl: Idl) {
+        self.program_errors
+            .register_idl_errors(program_id, idl.error_definitions());
+        self.program_errors.register_anchor_program(program_id);
+        self.idls.register(program_id, idl);
+    }
+
+    pub fn register_idl_from_file<P: AsRef<Path>>(
+        &mut self,
+        program_id: Pubkey,
+        path: P,
+    ) -> anyhow::Result<()> {
+        let idl = Idl::from_json(&std::fs::read_to_string(path)?)?;
+        self.register_idl(program_id, idl);
+        Ok(())
+    }
+
+    /// Registers the IDL the program published on the cluster.
+    pub fn fetch_idl(&mut self, program_id: Pubkey) -> anyhow::Result<()> {
+        let account = self.rpc_client.get_account(&Idl::address(&program_id))?;
+        self.register_idl(program_id, Idl::from_account_data(&account.data)?);
+        Ok(())
+    }
+
+    /// Registers a new blockhash, closing the working bank's block if blocks are produced per
+    /// blockhash.
+    pub fn advance_blockhash(&mut self, hash: Option<Hash>) -> Hash {
+        self.register_blockhash(hash);
+        if self.block_production == BlockProduction::PerBlockhash {
+            self.freeze_working_bank(true);
+        }
+        self.get_latest_blockhash()
+    }
+
+    fn register_blockhash(&mut self, hash: Option<Hash>) {
         let parent_distance = if self.bank.slot() == 0 {
             1
         } else {
@@ -112,50 +475,482 @@ impl Executor {
         };
 
         for _ in 0..parent_distance {
-            let last_blockhash = self.bank.last_blockhash();
             let new_hash = match hash {
                 Some(new_hash) if new_hash != self.bank.last_blockhash() => new_hash,
-                _ => Hash::new_unique(),
+                _ if self.block_production == BlockProduction::Disabled => Hash::new_unique(),
+                // The ticks register the blockhash themselves
+                _ => {
+                    let new_hash = self.register_ticks();
+                    self.registered_blockhashes.push(new_hash);
+                    continue;
+                }
             };
+            Self::register_blockhash_on_bank(&self.bank, &new_hash);
+            self.registered_blockhashes.push(new_hash);
+        }
+    }
 
-            while self.bank.last_blockhash() == last_blockhash {
-                self.bank.register_tick(&new_hash)
+    /// Registers ticks recorded as entries of the block being built up to the next blockhash,
+    /// the hash of the last tick.
+    fn register_ticks(&mut self) -> Hash {
+        let previous_blockhash = self.previous_blockhash();
+        let last_blockhash = self.bank.last_blockhash();
+        loop {
+            let tick_hash = self.blocks.record_tick(&previous_blockhash);
+            self.bank.register_tick(&tick_hash);
+            if self.bank.last_blockhash() != last_blockhash {
+                return tick_hash;
             }
         }
-        self.get_latest_blockhash()
     }
 
-    pub fn execute_transaction_internal(
+    /// Blockhash the block being built chains from.
+    fn previous_blockhash(&self) -> Hash {
+        self.bank
+            .parent()
+            .map(|parent| parent.last_blockhash())
+            .unwrap_or_default()
+    }
+
+    fn register_blockhash_on_bank(bank: &Bank, hash: &Hash) {
+        let last_blockhash = bank.last_blockhash();
+        while bank.last_blockhash() == last_blockhash {
+            bank.register_tick(hash)
+        }
+    }
+
+    pub fn execute_transaction_internal(&mut self, tx: &Transaction) -> ExecutionResult {
+        let bank = Arc::get_mut(&mut self.bank).expect("Working bank must not be shared");
+        let result = Self::execute_transaction_on_bank(
+            bank,
+            tx,
+            self.compute_budget_overrides.as_ref(),
+            &self.program_errors,
+            &self.idls,
+        );
+        self.history.record(&result);
+        // Blocks only hold the transactions the bank executed
+        if result.executed && self.block_production != BlockProduction::Disabled {
+            let previous_blockhash = self.previous_blockhash();
+            self.blocks.record_transaction(&result, &previous_blockhash);
+        }
+
+        result
+    }
+
+    pub fn execute_transaction_batch(&mut self, batch: &[Transaction]) -> Vec<ExecutionResult> {
+        self.load_accounts_from_cluster(&self.bank, batch);
+
+        let results = batch
+            .iter()
+            .map(|tx| self.execute_transaction_internal(tx))
+            .collect_vec();
+        if self.block_production == BlockProduction::PerBatch {
+            self.close_block();
+        }
+
+        results
+    }
+
+    /// Queues the transaction for the next sealed block, without executing it.
+    pub fn submit_transaction(&mut self, tx: Transaction) -> anyhow::Result<Signature> {
+        self.transaction_queue.submit(tx)
+    }
+
+    pub fn pending_transactions(&self) -> &[Transaction] {
+        self.transaction_queue.pending()
+    }
+
+    /// Executes the pending transactions in the order of the policy, then closes the block.
+    pub fn seal_block(&mut self, ordering: &OrderingPolicy) -> anyhow::Result<SealedBlock> {
+        let batch = self.transaction_queue.take_ordered(ordering)?;
+        Ok(self.seal_transactions(&batch))
+    }
+
+    /// Executes all the pending transactions ordered by `compare`, then closes the block.
+    pub fn seal_block_by<F>(&mut self, compare: F) -> SealedBlock
+    where
+        F: FnMut(&Transaction, &Transaction) -> Ordering,
+    {
+        let batch = self.transaction_queue.take_sorted_by(compare);
+        self.seal_transactions(&batch)
+    }
+
+    fn seal_transactions(&mut self, batch: &[Transaction]) -> SealedBlock {
+        let slot = self.bank.slot();
+        let results = self.execute_transaction_batch(batch);
+        // The batch already closed the block
+        if self.block_production != BlockProduction::PerBatch {
+            self.close_block();
+        }
+
+        SealedBlock { slot, results }
+    }
+
+    /// Executes the batch sequentially on a throw-away fork of the working bank, so each
+    /// transaction sees its predecessors' effects while the working bank is left untouched.
+    /// `account_overrides` are applied to the fork after accounts are loaded from the cluster.
+    pub fn simulate_transaction_batch(
+        &mut self,
+        batch: &[Transaction],
+        account_overrides: &[(Pubkey, AccountOverride)],
+    ) -> anyhow::Result<Vec<SimulationResult>> {
+        let mut bank = self.fork(1);
+        self.load_accounts_from_cluster(&bank, batch);
+        for (address, account_override) in account_overrides {
+            let account = apply_account_override(
+                bank.get_account(address).map(From::from),
+                account_override,
+            )?;
+            bank.store_account(address, &account);
+        }
+
+        Ok(batch
+            .iter()
+            .map(|tx| {
+                let result = Self::execute_transaction_on_bank(
+                    &mut bank,
+                    tx,
+                    self.compute_budget_overrides.as_ref(),
+                    &self.program_errors,
+                    &self.idls,
+                );
+                let post_accounts = tx
+                    .message
+                    .account_keys
+                    .iter()
+                    .map(|pubkey| (*pubkey, bank.get_account(pubkey).map(From::from)))
+                    .collect_vec();
+
+                SimulationResult {
+                    result,
+                    post_accounts,
+                }
+            })
+            .collect_vec())
+    }
+
+    /// Simulates the bundle like `simulate_transaction_batch`, then checks it against the rules.
+    pub fn simulate_bundle(
+        &mut self,
+        bundle: &[Transaction],
+        account_overrides: &[(Pubkey, AccountOverride)],
+        rules: &BundleRules,
+    ) -> anyhow::Result<BundleSimulationResult> {
+        let results = self.simulate_transaction_batch(bundle, account_overrides)?;
+        let (tip_lamports, violations) = validate_bundle(bundle, &results, rules);
+
+        Ok(BundleSimulationResult {
+            results,
+            tip_lamports,
+            violations,
+        })
+    }
+
+    /// Simulates the batch and builds the graph of its transactions, the programs they invoke
+    /// and the accounts they access.
+    pub fn get_account_graph(
+        &mut self,
+        batch: &[Transaction],
+        account_overrides: &[(Pubkey, AccountOverride)],
+    ) -> anyhow::Result<AccountGraph> {
+        let results = self
+            .simulate_transaction_batch(batch, account_overrides)?
+            .into_iter()
+            .map(|simulation| simulation.result)
+            .collect_vec();
+        Ok(build_account_graph(&results))
+    }
+
+    /// Read and write locks of each transaction of the batch, their conflicts and the groups a
+    /// leader could execute in parallel.
+    pub fn analyze_schedule(&self, batch: &[Transaction]) -> ScheduleAnalysis {
+        analyze_schedule(batch)
+    }
+
+    /// Executes the batch one parallel group at a time, each group through a single locked
+    /// batch like the banking stage, and sequentially, on sibling forks of the working bank.
+    /// Both executions are compared by transaction status and final state of the locked
+    /// accounts. Each transaction runs under the budget it requests, compute budget overrides
+    /// can't apply to a group of transactions.
+    pub fn simulate_schedule(&mut self, batch: &[Transaction]) -> ScheduleSimulationResult {
+        let analysis = analyze_schedule(batch);
+        let cluster_accounts = self.fetch_accounts_from_cluster(batch);
+        // Forks share the state and the clock of the working bank, so both executions start
+        // from the same state
+        let mut parallel_bank = self.fork(1);
+        let mut sequential_bank = self.fork(2);
+        for (address, account) in &cluster_accounts {
+            parallel_bank.store_account(address, account);
+            sequential_bank.store_account(address, account);
+        }
+
+        // Forks inherit the budget last set on the working bank
+        parallel_bank.set_compute_budget(None);
+        let mut parallel_results: Vec<Option<ExecutionResult>> =
+            batch.iter().map(|_| None).collect();
+        for group in &analysis.groups {
+            let txs = group
+                .iter()
+                .map(|index| batch[*index].clone())
+                .collect_vec();
+            let results =
+                Self::execute_batch_on_bank(&parallel_bank, &txs, &self.program_errors, &self.idls);
+            for (index, result) in group.iter().zip(results) {
+                parallel_results[*index] = Some(result);
+            }
+        }
+        let parallel_results = parallel_results
+            .into_iter()
+            .map(|result| result.expect("Each transaction belongs to a group"))
+            .collect_vec();
+
+        let sequential_results = batch
+            .iter()
+            .map(|tx| {
+                Self::execute_transaction_on_bank(
+                    &mut sequential_bank,
+                    tx,
+                    None,
+                    &self.program_errors,
+                    &self.idls,
+                )
+            })
+            .collect_vec();
+
+        let parallel_errors = parallel_results
+            .iter()
+            .map(|result| result.status.clone().err())
+            .collect_vec();
+        let sequential_errors = sequential_results
+            .iter()
+            .map(|result| result.status.clone().err())
+            .collect_vec();
+        let mismatched_transactions = (0..batch.len())
+            .filter(|index| parallel_errors[*index] != sequential_errors[*index])
+            .collect();
+        let mismatched_accounts = analysis
+            .locks
+            .iter()
+            .flat_map(|locks| locks.writable.iter())
+            .unique()
+            .filter(|pubkey| {
+                parallel_bank.get_account(pubkey) != sequential_bank.get_account(pubkey)
+            })
+            .copied()
+            .collect();
+
+        ScheduleSimulationResult {
+            analysis,
+            parallel_results,
+            sequential_results,
+            comparison: ScheduleComparison {
+                parallel_errors,
+                sequential_errors,
+                mismatched_transactions,
+                mismatched_accounts,
+            },
+        }
+    }
+
+    /// Executes the transaction on a throw-away fork with the maximal compute unit limit, and
+    /// recommends a limit of the consumed units plus `margin_percent`. Fails on the compute
+    /// budget requests a leader rejects.
+    pub fn estimate_compute_units(
         &mut self,
         tx: &Transaction,
-    ) -> EncodedConfirmedTransactionWithStatusMeta {
-        let len = bincode::serialize(&tx).unwrap().len();
-        if len > packet::PACKET_DATA_SIZE {
-            panic!(
-                "tx {:?} of size {} is {} too large",
-                tx,
-                len,
-                len - packet::PACKET_DATA_SIZE
-            )
+        margin_percent: u64,
+    ) -> anyhow::Result<ComputeUnitsEstimate> {
+        let compute_budget_overrides = ComputeBudgetOverrides {
+            compute_unit_limit: Some(MAX_COMPUTE_UNIT_LIMIT),
+            max_compute_unit_limit: Some(MAX_COMPUTE_UNIT_LIMIT),
+            ..self.compute_budget_overrides.unwrap_or_default()
+        };
+        compute_budget_with_overrides(&tx.message, &compute_budget_overrides)
+            .map_err(|error| anyhow!("Invalid compute budget request: {}", error))?;
+
+        let mut bank = self.fork(1);
+        self.load_accounts_from_cluster(&bank, std::slice::from_ref(tx));
+        let result = Self::execute_transaction_on_bank(
+            &mut bank,
+            tx,
+            Some(&compute_budget_overrides),
+            &self.program_errors,
+            &self.idls,
+        );
+
+        // Saturates, the limit is capped anyway
+        let recommended_compute_unit_limit = (result
+            .compute_units_consumed
+            .saturating_mul(margin_percent.saturating_add(100))
+            .saturating_add(99)
+            / 100)
+            .min(MAX_COMPUTE_UNIT_LIMIT as u64) as u32;
+
+        Ok(ComputeUnitsEstimate {
+            compute_units_consumed: result.compute_units_consumed,
+            recommended_compute_unit_limit,
+            error: result.status.err(),
+        })
+    }
+
+    /// Executes orderings of the candidates, each on its own fork of the working bank, and
+    /// values the balance changes of the wallet to find the most profitable one.
+    pub fn search_ordering(
+        &mut self,
+        request: &OrderingSearchRequest,
+    ) -> anyhow::Result<OrderingSearchResult> {
+        let len = request.candidates.len();
+        let strategy = request
+            .strategy
+            .unwrap_or_else(|| SearchStrategy::for_candidates(len));
+        if strategy == SearchStrategy::Exhaustive && len > MAX_EXHAUSTIVE_SEARCH_CANDIDATES {
+            bail!(
+                "Exhaustive search is limited to {} candidates, use a beam search",
+                MAX_EXHAUSTIVE_SEARCH_CANDIDATES
+            );
+        }
+
+        let cluster_accounts = self.fetch_accounts_from_cluster(&request.candidates);
+        let token_prices: HashMap<Pubkey, f64> = request.token_prices.iter().copied().collect();
+        let outcomes = search_orderings(len, strategy, |ordering| {
+            // Forks share the state and the clock of the working bank, so each ordering starts
+            // from the same state
+            let mut bank = self.fork(1);
+            for (address, account) in &cluster_accounts {
+                bank.store_account(address, account);
+            }
+            let results = ordering
+                .iter()
+                .map(|index| {
+                    Self::execute_transaction_on_bank(
+                        &mut bank,
+                        &request.candidates[*index],
+                        self.compute_budget_overrides.as_ref(),
+                        &self.program_errors,
+                        &self.idls,
+                    )
+                })
+                .collect_vec();
+            ordering_outcome(ordering, &results, &request.wallet, &token_prices)
+        });
+
+        Ok(OrderingSearchResult {
+            best: best_outcome(&outcomes, len)
+                .expect("Search must evaluate a complete ordering")
+                .clone(),
+            outcomes,
+        })
+    }
+
+    /// Returns a sibling of the working bank holding its state: the accounts it changed, the
+    /// blockhashes it registered and its clock. The working bank is left untouched, and changes
+    /// made to the returned bank are discarded when it is dropped.
+    ///
+    /// The fork takes the slot `offset` slots after the working bank's one, without using it
+    /// up. Banks of the same slot share their accounts storage, so forks living at the same
+    /// time need distinct offsets.
+    fn fork(&self, offset: Slot) -> Bank {
+        let parent = self.bank.parent().expect("Working bank must have a parent");
+        let bank = Bank::new_from_parent(&parent, parent.collector_id(), self.last_slot + offset);
+        for (pubkey, account) in self.bank.get_all_accounts_modified_since_parent() {
+            // The fork updates its own sysvars
+            if account.owner() != &SYSVAR_PID {
+                bank.store_account(&pubkey, &account);
+            }
+        }
+        for hash in &self.registered_blockhashes {
+            Self::register_blockhash_on_bank(&bank, hash);
+        }
+        // Programs see the working bank's clock, whatever the slot of the fork
+        bank.set_sysvar_for_tests(&self.bank.clock());
+
+        bank
+    }
+
+    /// Freezes the working bank and replaces it with a child, returning the frozen bank.
+    /// It is recorded as a block when `close_block` is set, unless block production is disabled.
+    fn freeze_working_bank(&mut self, close_block: bool) -> Arc<Bank> {
+        let parent = self.bank.clone();
+        parent.freeze();
+        parent.squash();
+        if close_block && self.block_production != BlockProduction::Disabled {
+            self.blocks.close_block(&parent, unix_timestamp_now());
+        } else {
+            self.blocks.discard_pending_transactions();
+        }
+        self.bank = Arc::new(self.new_child_bank(&parent));
+        self.registered_blockhashes.clear();
+
+        parent
+    }
+
+    fn new_child_bank(&mut self, parent: &Arc<Bank>) -> Bank {
+        self.last_slot += 1;
+        Bank::new_from_parent(parent, parent.collector_id(), self.last_slot)
+    }
+
+    fn execute_transaction_on_bank(
+        bank: &mut Bank,
+        tx: &Transaction,
+        compute_budget_overrides: Option<&ComputeBudgetOverrides>,
+        program_errors: &ProgramErrorRegistry,
+        idls: &IdlRegistry,
+    ) -> ExecutionResult {
+        // Without a budget, the bank rejects an invalid request itself, with the same error
+        bank.set_compute_budget(
+            compute_budget_overrides
+                .and_then(|overrides| compute_budget_with_overrides(&tx.message, overrides).ok()),
+        );
+        Self::execute_batch_on_bank(bank, std::slice::from_ref(tx), program_errors, idls)
+            .pop()
+            .expect("transaction could not be executed. Enable debug logging to get more information on why")
+    }
+
+    /// Executes the transactions through a single locked batch, the way a leader executes
+    /// non-conflicting transactions in parallel, under the compute budget set on the bank. The
+    /// bank's timings are attributed to the first result.
+    fn execute_batch_on_bank(
+        bank: &Bank,
+        txs: &[Transaction],
+        program_errors: &ProgramErrorRegistry,
+        idls: &IdlRegistry,
+    ) -> Vec<ExecutionResult> {
+        for tx in txs {
+            let len = bincode::serialize(&tx).unwrap().len();
+            if len > packet::PACKET_DATA_SIZE {
+                panic!(
+                    "tx {:?} of size {} is {} too large",
+                    tx,
+                    len,
+                    len - packet::PACKET_DATA_SIZE
+                )
+            }
         }
-        let txs = vec![tx.clone()];
+        let txs = txs.to_vec();
 
-        let batch = self.bank.prepare_batch_for_tests(txs.clone());
+        let batch = bank.prepare_batch_for_tests(txs.clone());
         let mut mint_decimals = HashMap::new();
         let tx_pre_token_balances =
-            token_balances::collect_token_balances(&self.bank, &batch, &mut mint_decimals);
-        let slot = self.bank.slot();
-        let mut timings = Default::default();
+            token_balances::collect_token_balances(bank, &batch, &mut mint_decimals);
+        let tx_pre_accounts = txs
+            .iter()
+            .map(|tx| Self::get_writable_accounts(bank, tx))
+            .collect_vec();
+        let slot = bank.slot();
+        let mut timings = ExecuteTimings::default();
         let (
             TransactionResults {
-                execution_results, ..
+                execution_results,
+                rent_debits,
+                ..
             },
             TransactionBalancesSet {
                 pre_balances,
                 post_balances,
                 ..
             },
-        ) = self.bank.load_execute_and_commit_transactions(
+        ) = bank.load_execute_and_commit_transactions(
             &batch,
             usize::MAX,
             true,
@@ -167,7 +962,7 @@ impl Executor {
         );
 
         let tx_post_token_balances =
-            token_balances::collect_token_balances(&self.bank, &batch, &mut mint_decimals);
+            token_balances::collect_token_balances(bank, &batch, &mut mint_decimals);
         izip!(
             txs.iter(),
             execution_results.into_iter(),
@@ -175,6 +970,8 @@ impl Executor {
             post_balances.into_iter(),
             tx_pre_token_balances.into_iter(),
             tx_post_token_balances.into_iter(),
+            tx_pre_accounts.into_iter(),
+            rent_debits.into_iter(),
         )
         .map(
             |(
@@ -184,14 +981,17 @@ impl Executor {
                 post_balances,
                 pre_token_balances,
                 post_token_balances,
+                pre_accounts,
+                rent_debits,
             ): ZippedItem| {
-                let fee = self.bank.get_fee_for_message(&SanitizedMessage::try_from(tx.message().clone()).expect("Failed to sanitize transaction"))
+                let executed = execution_result.was_executed();
+                let fee = bank.get_fee_for_message(&SanitizedMessage::try_from(tx.message().clone()).expect("Failed to sanitize transaction"))
                     .expect("Fee calculation must succeed");
 
-                let (status, inner_instructions, log_messages, executed_units) = match execution_result {
-                    TransactionExecutionResult::Executed { details: TransactionExecutionDetails { status, inner_instructions, log_messages, executed_units, .. }, .. } =>
-                        (status, inner_instructions, log_messages, executed_units),
-                    TransactionExecutionResult::NotExecuted(err) => (Err(err), None, None, 0)
+                let (status, inner_instructions, log_messages, return_data, executed_units) = match execution_result {
+                    TransactionExecutionResult::Executed { details: TransactionExecutionDetails { status, inner_instructions, log_messages, return_data, executed_units, .. }, .. } =>
+                        (status, inner_instructions, log_messages, return_data, executed_units),
+                    TransactionExecutionResult::NotExecuted(err) => (Err(err), None, None, None, 0)
                 };
 
                 let inner_instructions = inner_instructions.map(|inner_instructions| {
@@ -206,50 +1006,89 @@ impl Executor {
                         .collect()
                 });
 
-                let tx_status_meta = TransactionStatusMeta {
+                let rent_paid = rent_debits
+                    .into_unordered_rewards_iter()
+                    .map(|(_, reward)| reward.lamports.unsigned_abs())
+                    .sum();
+                let fee_breakdown = build_fee_breakdown(
+                    &tx.message,
+                    bank.get_lamports_per_signature(),
+                    rent_paid,
+                    &pre_balances,
+                    &post_balances,
+                );
+                let balance_changes = build_balance_changes(
+                    &tx.message.account_keys,
+                    &pre_balances,
+                    &post_balances,
+                    &pre_token_balances,
+                    &post_token_balances,
+                );
+
+                let mut call_tree = build_call_tree(
+                    &tx.message.account_keys,
+                    &tx.message.instructions,
+                    inner_instructions.as_deref().unwrap_or_default(),
+                    log_messages.as_deref().unwrap_or_default(),
+                );
+                idls.decode_call_tree(&mut call_tree);
+
+                let profile = build_profile(executed_units, &call_tree, &timings);
+                let decoded_error = program_errors.decode(&status, &tx.message, &call_tree);
+
+                let account_diffs = izip!(pre_accounts, Self::get_writable_accounts(bank, tx))
+                    .filter_map(|((pubkey, pre), (_, post))| {
+                        diff_account(pubkey, pre.as_ref(), post.as_ref())
+                    })
+                    .collect_vec();
+
+                ExecutionResult {
+                    slot,
+                    block_time: Some(unix_timestamp_now()),
+                    transaction: VersionedTransaction::from(tx.clone()),
+                    executed,
                     status,
                     fee,
+                    fee_breakdown,
+                    balance_changes,
                     pre_balances,
                     post_balances,
-                    pre_token_balances: (pre_token_balances).into(),
-                    post_token_balances: (post_token_balances).into(),
+                    pre_token_balances,
+                    post_token_balances,
                     inner_instructions,
                     log_messages,
-                    rewards: None,
-                    loaded_addresses: LoadedAddresses {
-                        writable: vec![], // TODO
-                        readonly: vec![], // TODO
-                    },
-                    return_data: None,
-                    compute_units_consumed: executed_units.into()
-                };
-
-                ConfirmedTransactionWithStatusMeta {
-                    slot,
-                    tx_with_meta: TransactionWithStatusMeta::Complete(VersionedTransactionWithStatusMeta {
-                        transaction: VersionedTransaction::from(tx.clone()),
-                        meta: tx_status_meta,
-                    }),
-                    block_time: Some(
-                        SystemTime::now()
-                            .duration_since(UNIX_EPOCH)
-                            .unwrap()
-                            .as_secs()
-                            .try_into()
-                            .unwrap(),
-                    ),
+                    return_data,
+                    compute_units_consumed: executed_units,
+                    account_diffs,
+                    call_tree,
+                    profile,
+                    decoded_error,
+                    timings: std::mem::take(&mut timings),
                 }
-                .encode(UiTransactionEncoding::Binary, None)
-                .expect("Failed to encode transaction")
             },
         )
-        .next().expect("transaction could not be executed. Enable debug logging to get more information on why")
+        .collect_vec()
     }
 
-    pub fn execute_transaction_batch(
-        &mut self,
-        batch: &[Transaction],
-    ) -> Vec<EncodedConfirmedTransactionWithStatusMeta> {
+    fn get_writable_accounts(bank: &Bank, tx: &Transaction) -> Vec<(Pubkey, Option<Account>)> {
+        tx.message
+            .account_keys
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| tx.message.is_writable(*index))
+            .map(|(_, pubkey)| (*pubkey, bank.get_account(pubkey).map(From::from)))
+            .collect_vec()
+    }
+
+    fn load_accounts_from_cluster(&self, bank: &Bank, batch: &[Transaction]) {
+        for (address, account) in self.fetch_accounts_from_cluster(batch) {
+            bank.store_account(&address, &account)
+        }
+    }
+
+    /// Accounts of the batch on the target cluster, along with the program data accounts of
+    /// its programs.
+    fn fetch_accounts_from_cluster(&self, batch: &[Transaction]) -> Vec<(Pubkey, Account)> {
         // Extract account keys from batch
         let account_keys = batch
             .iter()
@@ -257,6 +1096,9 @@ impl Executor {
             .sorted()
             .dedup()
             .collect_vec();
+        if account_keys.is_empty() {
+            return vec![];
+        }
 
         // Fetch corresponding accounts from target cluster
         let account_infos = self
@@ -300,27 +1142,65 @@ impl Executor {
             })
             .collect_vec();
 
-        // Load all accounts' data in local bank
-        for (address, account_info) in [account_infos, account_infos_2].concat() {
-            self.bank_mut().store_account(
-                &address,
-                &Account {
-                    lamports: account_info.lamports,
-                    data: account_info.data.to_vec(),
-                    executable: account_info.executable,
-                    owner: account_info.owner,
-                    rent_epoch: account_info.rent_epoch,
-                },
-            )
-        }
-
-        batch
-            .iter()
-            .map(|tx| self.execute_transaction_internal(tx))
+        [account_infos, account_infos_2]
+            .concat()
+            .into_iter()
+            .map(|(address, account_info)| {
+                (
+                    address,
+                    Account {
+                        lamports: account_info.lamports,
+                        data: account_info.data.to_vec(),
+                        executable: account_info.executable,
+                        owner: account_info.owner,
+                        rent_epoch: account_info.rent_epoch,
+                    },
+                )
+            })
             .collect_vec()
     }
 }
 
+fn apply_account_override(
+    account: Option<Account>,
+    account_override: &AccountOverride,
+) -> anyhow::Result<Account> {
+    match account_override {
+        AccountOverride::Replace(account) => Ok(account.clone()),
+        AccountOverride::Patch(AccountPatch {
+            lamports,
+            owner,
+            data,
+        }) => {
+            let mut account = account.unwrap_or_else(|| Account::new(0, 0, &SYSTEM_PID));
+            if let Some(lamports) = lamports {
+                account.lamports = *lamports;
+            }
+            if let Some(owner) = owner {
+                account.owner = *owner;
+            }
+            for patch in data {
+                let end = patch
+                    .offset
+                    .checked_add(patch.bytes.len())
+                    .filter(|end| *end as u64 <= MAX_PERMITTED_DATA_LENGTH)
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "Data patch at offset {} exceeds the maximum account size of {} bytes",
+                            patch.offset,
+                            MAX_PERMITTED_DATA_LENGTH
+                        )
+                    })?;
+                if account.data.len() < end {
+                    account.data.resize(end, 0);
+                }
+                account.data[patch.offset..end].copy_from_slice(&patch.bytes);
+            }
+            Ok(account)
+        }
+    }
+}
+
 type ZippedItem<'a> = (
     &'a Transaction,
     TransactionExecutionResult,
@@ -328,6 +1208,8 @@ type ZippedItem<'a> = (
     Vec<u64>,
     Vec<TransactionTokenBalance>,
     Vec<TransactionTokenBalance>,
+    Vec<(Pubkey, Option<Account>)>,
+    RentDebits,
 );
 
 pub struct ExecutorConfig {
@@ -404,15 +1286,13 @@ impl ExecutorBuilder {
             true,
         );
         builder.add_rent_exempt_account_with_data(
-            "Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo"
-                .parse()
-                .unwrap(),
+            SPL_MEMO1_PID,
             BPF_LOADER2_PID,
             programs::SPL_MEMO1,
             true,
         );
         builder.add_rent_exempt_account_with_data(
-            SPL_MEMO1_PID,
+            SPL_MEMO3_PID,
             BPF_LOADER2_PID,
             programs::SPL_MEMO3,
             true,
@@ -514,7 +1394,11 @@ impl ExecutorBuilder {
             }),
             AccountSecondaryIndexes {
                 keys: None,
-                indexes: HashSet::new(),
+                indexes: HashSet::from([
+                    AccountIndex::ProgramId,
+                    AccountIndex::SplTokenOwner,
+                    AccountIndex::SplTokenMint,
+                ]),
             },
             false,
             AccountShrinkThreshold::default(),
@@ -523,8 +1407,18 @@ impl ExecutorBuilder {
             None,
         );
 
-        let executor = Executor {
-            bank,
+        let mut executor = Executor {
+            last_slot: bank.slot(),
+            registered_blockhashes: vec![],
+            compute_budget_overrides: None,
+            program_errors: ProgramErrorRegistry::default(),
+            idls: IdlRegistry::default(),
+            account_parsers: HashMap::new(),
+            history: TransactionHistory::default(),
+            block_production: BlockProduction::default(),
+            blocks: BlockStore::default(),
+            transaction_queue: TransactionQueue::default(),
+            bank: Arc::new(bank),
             faucet: clone_keypair(&self.faucet),
             rpc_client: RpcClient::new_with_commitment(
                 self.rpc_endpoint.clone(),
@@ -533,8 +1427,104 @@ impl ExecutorBuilder {
                 },
             ),
         };
+        // Forks are siblings of the working bank, which therefore needs a parent
+        executor.freeze_working_bank(false);
         executor.advance_blockhash(None);
 
         executor
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use executor_client::DataPatch;
+
+    #[test]
+    fn test_forks_dont_use_up_slots() {
+        let mut executor = ExecutorBuilder::new().build();
+        let slot = executor.bank().slot();
+
+        let search = executor
+            .search_ordering(&OrderingSearchRequest {
+                candidates: vec![],
+                wallet: Pubkey::new_unique(),
+                token_prices: vec![],
+                strategy: None,
+            })
+            .unwrap();
+        executor.simulate_transaction_batch(&[], &[]).unwrap();
+        executor.simulate_schedule(&[]);
+        for _ in 0..3 {
+            drop(executor.fork(1));
+        }
+
+        assert_eq!(search.outcomes.len(), 1);
+        assert_eq!(executor.bank().slot(), slot);
+        assert_eq!(executor.close_block(), slot);
+        assert_eq!(executor.bank().slot(), slot + 1);
+    }
+
+    fn patch(lamports: Option<u64>, data: Vec<DataPatch>) -> AccountOverride {
+        AccountOverride::Patch(AccountPatch {
+            lamports,
+            owner: None,
+            data,
+        })
+    }
+
+    #[test]
+    fn test_apply_account_override_replace() {
+        let replacement = Account::new(42, 3, &Pubkey::new_unique());
+        let account = apply_account_override(
+            Some(Account::new(1, 0, &SYSTEM_PID)),
+            &AccountOverride::Replace(replacement.clone()),
+        )
+        .unwrap();
+        assert_eq!(account, replacement);
+    }
+
+    #[test]
+    fn test_apply_account_override_patch() {
+        let owner = Pubkey::new_unique();
+        let account = apply_account_override(
+            Some(Account::new(1, 4, &owner)),
+            &patch(
+                Some(7),
+                vec![DataPatch {
+                    offset: 2,
+                    bytes: vec![1, 2, 3, 4],
+                }],
+            ),
+        )
+        .unwrap();
+        assert_eq!(account.lamports, 7);
+        assert_eq!(account.owner, owner);
+        assert_eq!(account.data, vec![0, 0, 1, 2, 3, 4]);
+
+        let account = apply_account_override(None, &patch(None, vec![])).unwrap();
+        assert_eq!(account, Account::new(0, 0, &SYSTEM_PID));
+    }
+
+    #[test]
+    fn test_apply_account_override_data_size_limit() {
+        let at_limit = DataPatch {
+            offset: MAX_PERMITTED_DATA_LENGTH as usize - 1,
+            bytes: vec![1],
+        };
+        let account = apply_account_override(None, &patch(None, vec![at_limit])).unwrap();
+        assert_eq!(account.data.len() as u64, MAX_PERMITTED_DATA_LENGTH);
+
+        let past_limit = DataPatch {
+            offset: MAX_PERMITTED_DATA_LENGTH as usize,
+            bytes: vec![1],
+        };
+        assert!(apply_account_override(None, &patch(None, vec![past_limit])).is_err());
+
+        let overflowing = DataPatch {
+            offset: usize::MAX,
+            bytes: vec![1],
+        };
+        assert!(apply_account_override(None, &patch(None, vec![overflowing])).is_err());
+    }
+}
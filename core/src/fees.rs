@@ -0,0 +1,139 @@
+use crate::compute_budget::{compute_budget_with_overrides, prioritization_fee};
+use executor_client::FeeBreakdown;
+use solana_sdk::{ed25519_program, message::Message, secp256k1_program};
+
+/// Breaks down the fee a leader charges for the transaction, with the rent it paid and the
+/// resulting balance change of its fee payer.
+pub fn build_fee_breakdown(
+    message: &Message,
+    lamports_per_signature: u64,
+    rent_paid: u64,
+    pre_balances: &[u64],
+    post_balances: &[u64],
+) -> FeeBreakdown {
+    let signature_fee = num_signatures(message).saturating_mul(lamports_per_signature);
+    // Transactions requesting an invalid budget aren't executed, nor charged
+    let compute_unit_limit = compute_budget_with_overrides(message, &Default::default())
+        .map_or(0, |compute_budget| compute_budget.compute_unit_limit);
+    let (prioritization_fee, compute_unit_price) = prioritization_fee(message, compute_unit_limit);
+
+    FeeBreakdown {
+        signature_fee,
+        prioritization_fee,
+        compute_unit_limit,
+        compute_unit_price,
+        total_fee: signature_fee.saturating_add(prioritization_fee),
+        rent_paid,
+        payer_balance_delta: match (pre_balances.first(), post_balances.first()) {
+            (Some(pre), Some(post)) => *post as i64 - *pre as i64,
+            _ => 0,
+        },
+    }
+}
+
+/// Transaction signatures plus the signatures verified by precompiles, which are charged alike.
+fn num_signatures(message: &Message) -> u64 {
+    let precompile_signatures: u64 = message
+        .instructions
+        .iter()
+        .filter(|instruction| {
+            let program_id = &message.account_keys[instruction.program_id_index as usize];
+            secp256k1_program::check_id(program_id) || ed25519_program::check_id(program_id)
+        })
+        .map(|instruction| instruction.data.first().copied().unwrap_or_default() as u64)
+        .sum();
+
+    message.header.num_required_signatures as u64 + precompile_signatures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{
+        compute_budget::ComputeBudgetInstruction, instruction::Instruction, pubkey::Pubkey,
+        system_instruction,
+    };
+
+    const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+    fn message(instructions: &[Instruction]) -> Message {
+        Message::new(instructions, Some(&Pubkey::new_unique()))
+    }
+
+    fn transfer() -> Instruction {
+        system_instruction::transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1)
+    }
+
+    #[test]
+    fn test_prioritization_fee_rounds_up() {
+        let message = message(&[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_000),
+            ComputeBudgetInstruction::set_compute_unit_price(1_500),
+        ]);
+
+        let fee_breakdown = build_fee_breakdown(&message, LAMPORTS_PER_SIGNATURE, 0, &[], &[]);
+
+        assert_eq!(fee_breakdown.compute_unit_limit, 1_000);
+        assert_eq!(fee_breakdown.compute_unit_price, 1_500);
+        // 1.5 lamports
+        assert_eq!(fee_breakdown.prioritization_fee, 2);
+        assert_eq!(fee_breakdown.total_fee, LAMPORTS_PER_SIGNATURE + 2);
+    }
+
+    #[test]
+    fn test_invalid_budget_has_no_prioritization_fee() {
+        let message = message(&[
+            ComputeBudgetInstruction::set_compute_unit_price(1_500),
+            ComputeBudgetInstruction::set_compute_unit_price(3_000),
+            transfer(),
+        ]);
+
+        let fee_breakdown = build_fee_breakdown(&message, LAMPORTS_PER_SIGNATURE, 0, &[], &[]);
+
+        assert_eq!(fee_breakdown.compute_unit_limit, 0);
+        assert_eq!(fee_breakdown.prioritization_fee, 0);
+    }
+
+    #[test]
+    fn test_precompile_signatures_are_charged() {
+        let message = message(&[
+            Instruction::new_with_bytes(ed25519_program::id(), &[2, 0], vec![]),
+            Instruction::new_with_bytes(secp256k1_program::id(), &[1], vec![]),
+            Instruction::new_with_bytes(secp256k1_program::id(), &[], vec![]),
+            transfer(),
+        ]);
+
+        let fee_breakdown = build_fee_breakdown(&message, LAMPORTS_PER_SIGNATURE, 0, &[], &[]);
+
+        // The fee payer, the transfer's source and three precompile signatures
+        assert_eq!(fee_breakdown.signature_fee, 5 * LAMPORTS_PER_SIGNATURE);
+        assert_eq!(fee_breakdown.total_fee, 5 * LAMPORTS_PER_SIGNATURE);
+    }
+
+    #[test]
+    fn test_payer_balance_delta() {
+        let message = message(&[transfer()]);
+
+        // A failed transaction only pays its fee
+        let failed = build_fee_breakdown(
+            &message,
+            LAMPORTS_PER_SIGNATURE,
+            0,
+            &[20_000, 0],
+            &[10_000, 0],
+        );
+        let rent_paying = build_fee_breakdown(
+            &message,
+            LAMPORTS_PER_SIGNATURE,
+            890_880,
+            &[1_000_000, 0],
+            &[99_120, 890_880],
+        );
+        let without_balances = build_fee_breakdown(&message, LAMPORTS_PER_SIGNATURE, 0, &[], &[]);
+
+        assert_eq!(failed.payer_balance_delta, -10_000);
+        assert_eq!(rent_paying.payer_balance_delta, -900_880);
+        assert_eq!(rent_paying.rent_paid, 890_880);
+        assert_eq!(without_balances.payer_balance_delta, 0);
+    }
+}
@@ -0,0 +1,264 @@
+use crate::result::ExecutionResult;
+use executor_client::SignatureInfo;
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction};
+use solana_transaction_status::{
+    extract_memos::extract_and_fmt_memos, ConfirmedTransactionWithStatusMeta,
+    TransactionConfirmationStatus, TransactionStatus,
+};
+use std::collections::HashMap;
+
+struct HistoryEntry {
+    signature: Signature,
+    status: transaction::Result<()>,
+    memo: Option<String>,
+    transaction: ConfirmedTransactionWithStatusMeta,
+}
+
+/// Transactions executed on the working bank, in execution order, indexed by signature and by
+/// the addresses they reference.
+#[derive(Default)]
+pub struct TransactionHistory {
+    entries: Vec<HistoryEntry>,
+    by_signature: HashMap<Signature, usize>,
+    by_address: HashMap<Pubkey, Vec<usize>>,
+}
+
+impl TransactionHistory {
+    /// Records an executed transaction, unless it is unsigned or its signature was already
+    /// processed. Transactions which failed to load are skipped, as they may be resubmitted.
+    pub fn record(&mut self, result: &ExecutionResult) {
+        let index = self.entries.len();
+        let signature = match result.transaction.signatures.first() {
+            Some(signature) if result.executed => *signature,
+            _ => return,
+        };
+        if self.by_signature.contains_key(&signature) {
+            return;
+        }
+        for pubkey in result.transaction.message.static_account_keys() {
+            self.by_address.entry(*pubkey).or_default().push(index);
+        }
+        self.by_signature.insert(signature, index);
+        self.entries.push(HistoryEntry {
+            signature,
+            status: result.status.clone(),
+            memo: result
+                .transaction
+                .clone()
+                .into_legacy_transaction()
+                .and_then(|tx| extract_and_fmt_memos(&tx.message)),
+            transaction: result.to_confirmed_transaction(),
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get_transaction(
+        &self,
+        signature: &Signature,
+    ) -> Option<&ConfirmedTransactionWithStatusMeta> {
+        self.get_entry(signature).map(|entry| &entry.transaction)
+    }
+
+    /// Executed transactions are committed to the working bank right away, so they are
+    /// reported as finalized.
+    pub fn get_signature_status(&self, signature: &Signature) -> Option<TransactionStatus> {
+        let entry = self.get_entry(signature)?;
+        Some(TransactionStatus {
+            slot: entry.transaction.slot,
+            confirmations: None,
+            status: entry.status.clone(),
+            err: entry.status.clone().err(),
+            confirmation_status: Some(TransactionConfirmationStatus::Finalized),
+        })
+    }
+
+    /// Signatures of the transactions referencing `address`, newest first, starting before the
+    /// `before` signature and stopping at the `until` one, both excluded.
+    pub fn get_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before: Option<&Signature>,
+        until: Option<&Signature>,
+        limit: usize,
+    ) -> Vec<SignatureInfo> {
+        let before = before.and_then(|signature| self.by_signature.get(signature));
+        let until = until.and_then(|signature| self.by_signature.get(signature));
+
+        self.by_address
+            .get(address)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .rev()
+            .filter(|index| before.map_or(true, |before| *index < before))
+            .take_while(|index| until.map_or(true, |until| *index > until))
+            .take(limit)
+            .map(|index| {
+                let entry = &self.entries[*index];
+                SignatureInfo {
+                    signature: entry.signature,
+                    slot: entry.transaction.slot,
+                    err: entry.status.clone().err(),
+                    memo: entry.memo.clone(),
+                    block_time: entry.transaction.block_time,
+                }
+            })
+            .collect()
+    }
+
+    fn get_entry(&self, signature: &Signature) -> Option<&HistoryEntry> {
+        self.by_signature
+            .get(signature)
+            .map(|index| &self.entries[*index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{
+        hash::Hash,
+        signature::{Keypair, Signer},
+        system_transaction,
+        transaction::{TransactionError, VersionedTransaction},
+    };
+
+    fn transfer(to: &Pubkey) -> VersionedTransaction {
+        system_transaction::transfer(&Keypair::new(), to, 1, Hash::new_unique()).into()
+    }
+
+    /// History of five transfers to `address`, returned along with their signatures in
+    /// execution order, and of an unrelated one.
+    fn history(address: &Pubkey) -> (TransactionHistory, Vec<Signature>) {
+        let mut history = TransactionHistory::default();
+        let mut signatures = vec![];
+        for slot in 0..5 {
+            let transaction = transfer(address);
+            signatures.push(transaction.signatures[0]);
+            history.record(&ExecutionResult::for_tests(transaction, slot));
+        }
+        history.record(&ExecutionResult::for_tests(
+            transfer(&Pubkey::new_unique()),
+            5,
+        ));
+        (history, signatures)
+    }
+
+    fn signatures_for_address(
+        history: &TransactionHistory,
+        address: &Pubkey,
+        before: Option<&Signature>,
+        until: Option<&Signature>,
+        limit: usize,
+    ) -> Vec<Signature> {
+        history
+            .get_signatures_for_address(address, before, until, limit)
+            .into_iter()
+            .map(|info| info.signature)
+            .collect()
+    }
+
+    #[test]
+    fn test_get_signatures_for_address_newest_first() {
+        let address = Pubkey::new_unique();
+        let (history, signatures) = history(&address);
+
+        let newest_first: Vec<Signature> = signatures.iter().rev().copied().collect();
+        assert_eq!(
+            signatures_for_address(&history, &address, None, None, 10),
+            newest_first
+        );
+        assert_eq!(
+            signatures_for_address(&history, &address, None, None, 2),
+            newest_first[..2]
+        );
+        assert!(signatures_for_address(&history, &Pubkey::new_unique(), None, None, 10).is_empty());
+    }
+
+    #[test]
+    fn test_get_signatures_for_address_pagination() {
+        let address = Pubkey::new_unique();
+        let (history, signatures) = history(&address);
+
+        assert_eq!(
+            signatures_for_address(&history, &address, Some(&signatures[3]), None, 10),
+            vec![signatures[2], signatures[1], signatures[0]]
+        );
+        assert_eq!(
+            signatures_for_address(&history, &address, None, Some(&signatures[1]), 10),
+            vec![signatures[4], signatures[3], signatures[2]]
+        );
+        assert_eq!(
+            signatures_for_address(
+                &history,
+                &address,
+                Some(&signatures[4]),
+                Some(&signatures[1]),
+                10
+            ),
+            vec![signatures[3], signatures[2]]
+        );
+        assert_eq!(
+            signatures_for_address(
+                &history,
+                &address,
+                Some(&signatures[4]),
+                Some(&signatures[0]),
+                1
+            ),
+            vec![signatures[3]]
+        );
+    }
+
+    #[test]
+    fn test_record_skips_unsigned_and_duplicate_transactions() {
+        let address = Pubkey::new_unique();
+        let (mut history, signatures) = history(&address);
+        let mut unsigned = transfer(&address);
+        unsigned.signatures.clear();
+
+        let duplicate = history
+            .get_transaction(&signatures[0])
+            .unwrap()
+            .get_transaction();
+        history.record(&ExecutionResult::for_tests(unsigned, 6));
+        history.record(&ExecutionResult::for_tests(duplicate, 7));
+
+        assert_eq!(history.len(), 6);
+        assert_eq!(
+            history.get_signature_status(&signatures[0]).unwrap().slot,
+            0
+        );
+    }
+
+    #[test]
+    fn test_record_skips_transactions_which_failed_to_load() {
+        let address = Pubkey::new_unique();
+        let (mut history, _) = history(&address);
+        let transaction = transfer(&address);
+        let signature = transaction.signatures[0];
+        let mut not_executed = ExecutionResult::for_tests(transaction.clone(), 6);
+        not_executed.executed = false;
+        not_executed.status = Err(TransactionError::BlockhashNotFound);
+
+        history.record(&not_executed);
+        assert!(history.get_signature_status(&signature).is_none());
+        assert!(history.get_transaction(&signature).is_none());
+
+        history.record(&ExecutionResult::for_tests(transaction, 7));
+        let status = history.get_signature_status(&signature).unwrap();
+        assert_eq!(status.slot, 7);
+        assert_eq!(status.status, Ok(()));
+        assert_eq!(
+            signatures_for_address(&history, &address, None, None, 1),
+            vec![signature]
+        );
+    }
+}
@@ -0,0 +1,575 @@
+use anyhow::{anyhow, bail, Context};
+use executor_client::{
+    DecodedEvent, DecodedInstruction, DecodedValue, Invocation, ParsedAccount,
+    ProgramErrorDefinition,
+};
+use flate2::read::ZlibDecoder;
+use serde::Deserialize;
+use solana_sdk::{account::Account, hash::hash, pubkey::Pubkey};
+use std::{collections::HashMap, io::Read};
+
+const IDL_SEED: &str = "anchor:idl";
+const DISCRIMINATOR_LEN: usize = 8;
+const PROGRAM_DATA_LOG_PREFIX: &str = "Program data: ";
+
+/// Subset of the Anchor IDL format needed to decode instructions, events, accounts and errors.
+#[derive(Clone, Deserialize)]
+pub struct Idl {
+    pub name: String,
+    #[serde(default)]
+    pub instructions: Vec<IdlInstruction>,
+    #[serde(default)]
+    pub accounts: Vec<IdlTypeDefinition>,
+    #[serde(default)]
+    pub types: Vec<IdlTypeDefinition>,
+    #[serde(default)]
+    pub events: Vec<IdlEvent>,
+    #[serde(default)]
+    pub errors: Vec<IdlErrorCode>,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct IdlInstruction {
+    pub name: String,
+    pub accounts: Vec<IdlAccountItem>,
+    pub args: Vec<IdlField>,
+}
+
+/// Instruction account, or group of accounts when `accounts` isn't empty.
+#[derive(Clone, Deserialize)]
+pub struct IdlAccountItem {
+    pub name: String,
+    #[serde(default)]
+    pub accounts: Vec<IdlAccountItem>,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct IdlField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: IdlType,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct IdlEvent {
+    pub name: String,
+    pub fields: Vec<IdlField>,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct IdlErrorCode {
+    pub code: u32,
+    pub name: String,
+    #[serde(default)]
+    pub msg: Option<String>,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct IdlTypeDefinition {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: IdlTypeDefinitionTy,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum IdlTypeDefinitionTy {
+    Struct { fields: Vec<IdlField> },
+    Enum { variants: Vec<IdlEnumVariant> },
+}
+
+#[derive(Clone, Deserialize)]
+pub struct IdlEnumVariant {
+    pub name: String,
+    #[serde(default)]
+    pub fields: Option<IdlEnumFields>,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(untagged)]
+pub enum IdlEnumFields {
+    Named(Vec<IdlField>),
+    Tuple(Vec<IdlType>),
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(untagged)]
+pub enum IdlType {
+    Primitive(String),
+    Vec { vec: Box<IdlType> },
+    Option { option: Box<IdlType> },
+    Defined { defined: String },
+    Array { array: (Box<IdlType>, usize) },
+}
+
+impl Idl {
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        serde_json::from_str(json).context("Invalid IDL")
+    }
+
+    /// Parses the IDL account an Anchor program publishes with `anchor idl init`.
+    pub fn from_account_data(data: &[u8]) -> anyhow::Result<Self> {
+        // Discriminator, authority, then the zlib compressed IDL JSON as a borsh `Vec<u8>`
+        let len_offset = DISCRIMINATOR_LEN + 32;
+        let len = data
+            .get(len_offset..len_offset + 4)
+            .ok_or_else(|| anyhow!("IDL account is too small"))?;
+        let len = u32::from_le_bytes(len.try_into().unwrap()) as usize;
+        let compressed = data
+            .get(len_offset + 4..len_offset + 4 + len)
+            .ok_or_else(|| anyhow!("IDL account data is truncated"))?;
+
+        let mut json = String::new();
+        ZlibDecoder::new(compressed)
+            .read_to_string(&mut json)
+            .context("Failed to decompress IDL")?;
+        Self::from_json(&json)
+    }
+
+    /// Address of the IDL account of an Anchor program.
+    pub fn address(program_id: &Pubkey) -> Pubkey {
+        let base = Pubkey::find_program_address(&[], program_id).0;
+        Pubkey::create_with_seed(&base, IDL_SEED, program_id).unwrap()
+    }
+
+    pub fn error_definitions(&self) -> Vec<ProgramErrorDefinition> {
+        self.errors
+            .iter()
+            .map(|error| ProgramErrorDefinition {
+                code: error.code,
+                name: error.name.clone(),
+                message: error.msg.clone().unwrap_or_else(|| error.name.clone()),
+            })
+            .collect()
+    }
+}
+
+/// IDL along with its instruction, event and account discriminators.
+struct RegisteredIdl {
+    idl: Idl,
+    instructions: HashMap<[u8; DISCRIMINATOR_LEN], usize>,
+    events: HashMap<[u8; DISCRIMINATOR_LEN], usize>,
+    accounts: HashMap<[u8; DISCRIMINATOR_LEN], usize>,
+    /// Types fields may refer to, including account types.
+    definitions: Vec<IdlTypeDefinition>,
+}
+
+/// Anchor IDLs of known programs, used to decode their instructions, events and accounts.
+#[derive(Default)]
+pub struct IdlRegistry {
+    idls: HashMap<Pubkey, RegisteredIdl>,
+}
+
+impl IdlRegistry {
+    pub fn register(&mut self, program_id: Pubkey, idl: Idl) {
+        let instructions = idl
+            .instructions
+            .iter()
+            .enumerate()
+            .map(|(index, ix)| (discriminator("global", &to_snake_case(&ix.name)), index))
+            .collect();
+        let events = idl
+            .events
+            .iter()
+            .enumerate()
+            .map(|(index, event)| (discriminator("event", &event.name), index))
+            .collect();
+        let accounts = idl
+            .accounts
+            .iter()
+            .enumerate()
+            .map(|(index, account)| (discriminator("account", &account.name), index))
+            .collect();
+        let definitions = idl.types.iter().chain(&idl.accounts).cloned().collect();
+
+        self.idls.insert(
+            program_id,
+            RegisteredIdl {
+                idl,
+                instructions,
+                events,
+                accounts,
+                definitions,
+            },
+        );
+    }
+
+    /// Decodes an account owned by a program with a registered IDL.
+    pub fn parse_account(&self, account: &Account) -> Option<ParsedAccount> {
+        let registered = self.idls.get(&account.owner)?;
+        let (discriminator, mut data) = split_discriminator(&account.data)?;
+        let definition = &registered.idl.accounts[*registered.accounts.get(&discriminator)?];
+        let fields = match &definition.ty {
+            IdlTypeDefinitionTy::Struct { fields } => fields,
+            IdlTypeDefinitionTy::Enum { .. } => return None,
+        };
+
+        Some(ParsedAccount {
+            program: registered.idl.name.clone(),
+            account_type: definition.name.clone(),
+            parsed: DecodedValue::Struct(
+                decode_fields(&registered.definitions, fields, &mut data).ok()?,
+            ),
+        })
+    }
+
+    /// Decodes the instructions and events of the call tree's invocations of known programs.
+    pub fn decode_call_tree(&self, call_tree: &mut [Invocation]) {
+        for invocation in call_tree {
+            if let Some(registered) = self.idls.get(&invocation.program_id) {
+                invocation.decoded_instruction = registered.decode_instruction(invocation);
+                invocation.events = invocation
+                    .logs
+                    .iter()
+                    .filter_map(|line| line.strip_prefix(PROGRAM_DATA_LOG_PREFIX))
+                    .filter_map(|data| base64::decode(data).ok())
+                    .filter_map(|data| registered.decode_event(&data))
+                    .collect();
+            }
+            self.decode_call_tree(&mut invocation.inner_invocations);
+        }
+    }
+}
+
+impl RegisteredIdl {
+    fn decode_instruction(&self, invocation: &Invocation) -> Option<DecodedInstruction> {
+        let (discriminator, mut data) = split_discriminator(&invocation.data)?;
+        let instruction = &self.idl.instructions[*self.instructions.get(&discriminator)?];
+        let args = decode_fields(&self.definitions, &instruction.args, &mut data).ok()?;
+
+        let mut names = vec![];
+        flatten_account_names(&instruction.accounts, "", &mut names);
+        let accounts = invocation
+            .accounts
+            .iter()
+            .enumerate()
+            .map(|(index, pubkey)| {
+                let name = names
+                    .get(index)
+                    .cloned()
+                    .unwrap_or_else(|| "remaining".to_string());
+                (name, *pubkey)
+            })
+            .collect();
+
+        Some(DecodedInstruction {
+            name: instruction.name.clone(),
+            args,
+            accounts,
+        })
+    }
+
+    fn decode_event(&self, data: &[u8]) -> Option<DecodedEvent> {
+        let (discriminator, mut data) = split_discriminator(data)?;
+        let event = &self.idl.events[*self.events.get(&discriminator)?];
+        Some(DecodedEvent {
+            name: event.name.clone(),
+            fields: decode_fields(&self.definitions, &event.fields, &mut data).ok()?,
+        })
+    }
+}
+
+/// Decodes borsh serialized fields, `definitions` being the types they may refer to.
+pub fn decode_fields(
+    definitions: &[IdlTypeDefinition],
+    fields: &[IdlField],
+    data: &mut &[u8],
+) -> anyhow::Result<Vec<(String, DecodedValue)>> {
+    fields
+        .iter()
+        .map(|field| {
+            Ok((
+                field.name.clone(),
+                decode_value(definitions, &field.ty, data)?,
+            ))
+        })
+        .collect()
+}
+
+fn decode_value(
+    definitions: &[IdlTypeDefinition],
+    ty: &IdlType,
+    data: &mut &[u8],
+) -> anyhow::Result<DecodedValue> {
+    Ok(match ty {
+        IdlType::Primitive(name) => match name.as_str() {
+            "bool" => DecodedValue::Bool(take::<1>(data)?[0] != 0),
+            "u8" => DecodedValue::Unsigned(take::<1>(data)?[0] as u128),
+            "u16" => DecodedValue::Unsigned(u16::from_le_bytes(take(data)?) as u128),
+            "u32" => DecodedValue::Unsigned(u32::from_le_bytes(take(data)?) as u128),
+            "u64" => DecodedValue::Unsigned(u64::from_le_bytes(take(data)?) as u128),
+            "u128" => DecodedValue::Unsigned(u128::from_le_bytes(take(data)?)),
+            "i8" => DecodedValue::Signed(i8::from_le_bytes(take(data)?) as i128),
+            "i16" => DecodedValue::Signed(i16::from_le_bytes(take(data)?) as i128),
+            "i32" => DecodedValue::Signed(i32::from_le_bytes(take(data)?) as i128),
+            "i64" => DecodedValue::Signed(i64::from_le_bytes(take(data)?) as i128),
+            "i128" => DecodedValue::Signed(i128::from_le_bytes(take(data)?)),
+            "f32" => DecodedValue::Float(f32::from_le_bytes(take(data)?) as f64),
+            "f64" => DecodedValue::Float(f64::from_le_bytes(take(data)?)),
+            "bytes" => DecodedValue::Bytes(take_vec(data)?),
+            "string" => DecodedValue::String(String::from_utf8(take_vec(data)?)?),
+            "publicKey" => DecodedValue::Pubkey(Pubkey::new_from_array(take(data)?)),
+            _ => bail!("Unsupported IDL type {}", name),
+        },
+        IdlType::Vec { vec } => {
+            let len = u32::from_le_bytes(take(data)?);
+            // Elements take at least a byte, except zero-sized ones which borsh rejects too
+            if len as usize > data.len() {
+                bail!("Vec of {} elements exceeds the remaining data", len);
+            }
+            DecodedValue::Array(
+                (0..len)
+                    .map(|_| decode_value(definitions, vec, data))
+                    .collect::<anyhow::Result<_>>()?,
+            )
+        }
+        IdlType::Option { option } => match take::<1>(data)?[0] {
+            0 => DecodedValue::Option(None),
+            _ => DecodedValue::Option(Some(Box::new(decode_value(definitions, option, data)?))),
+        },
+        IdlType::Array { array: (ty, len) } => DecodedValue::Array(
+            (0..*len)
+                .map(|_| decode_value(definitions, ty, data))
+                .collect::<anyhow::Result<_>>()?,
+        ),
+        IdlType::Defined { defined } => {
+            let definition = definitions
+                .iter()
+                .find(|definition| &definition.name == defined)
+                .ok_or_else(|| anyhow!("Undefined IDL type {}", defined))?;
+            match &definition.ty {
+                IdlTypeDefinitionTy::Struct { fields } => {
+                    DecodedValue::Struct(decode_fields(definitions, fields, data)?)
+                }
+                IdlTypeDefinitionTy::Enum { variants } => {
+                    let index = take::<1>(data)?[0] as usize;
+                    let variant = variants
+                        .get(index)
+                        .ok_or_else(|| anyhow!("Invalid {} variant {}", defined, index))?;
+                    let value = match &variant.fields {
+                        Some(IdlEnumFields::Named(fields)) => Some(DecodedValue::Struct(
+                            decode_fields(definitions, fields, data)?,
+                        )),
+                        Some(IdlEnumFields::Tuple(types)) => Some(DecodedValue::Tuple(
+                            types
+                                .iter()
+                                .map(|ty| decode_value(definitions, ty, data))
+                                .collect::<anyhow::Result<_>>()?,
+                        )),
+                        None => None,
+                    };
+                    DecodedValue::Enum {
+                        variant: variant.name.clone(),
+                        value: value.map(Box::new),
+                    }
+                }
+            }
+        }
+    })
+}
+
+fn take<const N: usize>(data: &mut &[u8]) -> anyhow::Result<[u8; N]> {
+    if data.len() < N {
+        bail!("Unexpected end of data");
+    }
+    let (bytes, rest) = data.split_at(N);
+    *data = rest;
+    Ok(bytes.try_into().unwrap())
+}
+
+fn take_vec(data: &mut &[u8]) -> anyhow::Result<Vec<u8>> {
+    let len = u32::from_le_bytes(take(data)?) as usize;
+    if data.len() < len {
+        bail!("Unexpected end of data");
+    }
+    let (bytes, rest) = data.split_at(len);
+    *data = rest;
+    Ok(bytes.to_vec())
+}
+
+fn split_discriminator(data: &[u8]) -> Option<([u8; DISCRIMINATOR_LEN], &[u8])> {
+    let mut data = data;
+    let discriminator = take(&mut data).ok()?;
+    Some((discriminator, data))
+}
+
+/// Anchor's 8 bytes discriminator, the prefix of `sha256("<namespace>:<name>")`.
+fn discriminator(namespace: &str, name: &str) -> [u8; DISCRIMINATOR_LEN] {
+    hash(format!("{}:{}", namespace, name).as_bytes()).to_bytes()[..DISCRIMINATOR_LEN]
+        .try_into()
+        .unwrap()
+}
+
+/// IDLs name instructions in camel case, while their discriminators use the Rust name.
+fn to_snake_case(name: &str) -> String {
+    let mut snake_case = String::new();
+    for (index, c) in name.chars().enumerate() {
+        if c.is_uppercase() && index > 0 {
+            snake_case.push('_');
+        }
+        snake_case.extend(c.to_lowercase());
+    }
+    snake_case
+}
+
+fn flatten_account_names(accounts: &[IdlAccountItem], prefix: &str, names: &mut Vec<String>) {
+    for account in accounts {
+        let name = format!("{}{}", prefix, account.name);
+        match account.accounts.is_empty() {
+            true => names.push(name),
+            false => flatten_account_names(&account.accounts, &format!("{}.", name), names),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDL_JSON: &str = r#"{
+        "name": "market",
+        "instructions": [{
+            "name": "placeOrder",
+            "accounts": [],
+            "args": [
+                {"name": "amount", "type": "u64"},
+                {"name": "label", "type": "string"},
+                {"name": "prices", "type": {"vec": "u16"}},
+                {"name": "referrer", "type": {"option": "publicKey"}},
+                {"name": "side", "type": {"defined": "Side"}},
+                {"name": "limits", "type": {"defined": "Limits"}}
+            ]
+        }],
+        "types": [
+            {
+                "name": "Side",
+                "type": {
+                    "kind": "enum",
+                    "variants": [
+                        {"name": "Bid"},
+                        {"name": "Ask", "fields": [{"name": "price", "type": "u64"}]}
+                    ]
+                }
+            },
+            {
+                "name": "Limits",
+                "type": {
+                    "kind": "struct",
+                    "fields": [{"name": "bounds", "type": {"array": ["i8", 2]}}]
+                }
+            }
+        ]
+    }"#;
+
+    fn instruction_args(idl: &Idl) -> &[IdlField] {
+        &idl.instructions[0].args
+    }
+
+    #[test]
+    fn test_decode_fields() {
+        let idl = Idl::from_json(IDL_JSON).unwrap();
+        let referrer = Pubkey::new_unique();
+        let mut data = vec![];
+        data.extend(42u64.to_le_bytes());
+        data.extend(3u32.to_le_bytes());
+        data.extend(b"buy");
+        data.extend(2u32.to_le_bytes());
+        data.extend(7u16.to_le_bytes());
+        data.extend(9u16.to_le_bytes());
+        data.push(1);
+        data.extend(referrer.to_bytes());
+        data.push(1);
+        data.extend(100u64.to_le_bytes());
+        data.extend([(-1i8) as u8, 1]);
+
+        let mut remaining = data.as_slice();
+        let fields = decode_fields(&idl.types, instruction_args(&idl), &mut remaining).unwrap();
+
+        assert!(remaining.is_empty());
+        let names: Vec<&str> = fields.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["amount", "label", "prices", "referrer", "side", "limits"]
+        );
+        assert!(matches!(fields[0].1, DecodedValue::Unsigned(42)));
+        assert!(matches!(&fields[1].1, DecodedValue::String(label) if label == "buy"));
+        match &fields[2].1 {
+            DecodedValue::Array(prices) => {
+                assert_eq!(prices.len(), 2);
+                assert!(matches!(prices[0], DecodedValue::Unsigned(7)));
+                assert!(matches!(prices[1], DecodedValue::Unsigned(9)));
+            }
+            _ => panic!("prices should decode to an array"),
+        }
+        match &fields[3].1 {
+            DecodedValue::Option(Some(value)) => {
+                assert!(matches!(**value, DecodedValue::Pubkey(pubkey) if pubkey == referrer))
+            }
+            _ => panic!("referrer should decode to a set option"),
+        }
+        match &fields[4].1 {
+            DecodedValue::Enum { variant, value } => {
+                assert_eq!(variant, "Ask");
+                match value.as_deref() {
+                    Some(DecodedValue::Struct(fields)) => {
+                        assert_eq!(fields[0].0, "price");
+                        assert!(matches!(fields[0].1, DecodedValue::Unsigned(100)));
+                    }
+                    _ => panic!("Ask should decode its named fields"),
+                }
+            }
+            _ => panic!("side should decode to an enum"),
+        }
+        match &fields[5].1 {
+            DecodedValue::Struct(fields) => match &fields[0].1 {
+                DecodedValue::Array(bounds) => {
+                    assert!(matches!(bounds[0], DecodedValue::Signed(-1)));
+                    assert!(matches!(bounds[1], DecodedValue::Signed(1)));
+                }
+                _ => panic!("bounds should decode to an array"),
+            },
+            _ => panic!("limits should decode to a struct"),
+        }
+    }
+
+    #[test]
+    fn test_decode_vec_longer_than_data() {
+        let ty = IdlType::Vec {
+            vec: Box::new(IdlType::Primitive("u8".to_string())),
+        };
+        let mut data = vec![];
+        data.extend(u32::MAX.to_le_bytes());
+        data.extend([1, 2, 3]);
+
+        let error = decode_value(&[], &ty, &mut data.as_slice()).err().unwrap();
+
+        assert_eq!(
+            error.to_string(),
+            format!("Vec of {} elements exceeds the remaining data", u32::MAX)
+        );
+    }
+
+    #[test]
+    fn test_decode_truncated_data() {
+        let idl = Idl::from_json(IDL_JSON).unwrap();
+        let data = 42u64.to_le_bytes();
+
+        let result = decode_fields(&idl.types, instruction_args(&idl), &mut data.as_slice());
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_decode_invalid_enum_variant() {
+        let idl = Idl::from_json(IDL_JSON).unwrap();
+        let ty = IdlType::Defined {
+            defined: "Side".to_string(),
+        };
+
+        let error = decode_value(&idl.types, &ty, &mut [2u8].as_slice())
+            .err()
+            .unwrap();
+
+        assert_eq!(error.to_string(), "Invalid Side variant 2");
+    }
+}
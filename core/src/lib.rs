@@ -1,3 +1,21 @@
+pub mod account_diff;
+pub mod account_graph;
+pub mod account_parser;
+pub mod balances;
+pub mod blocks;
+pub mod bundle;
+pub mod call_tree;
+pub mod compute_budget;
 pub mod executor;
+pub mod fees;
+pub mod history;
+pub mod idl;
+pub mod ordering_search;
+pub mod profiling;
+pub mod program_errors;
 pub mod programs;
+pub mod result;
+pub mod scheduling;
+pub mod spl_token;
+pub mod transaction_queue;
 pub mod utils;
@@ -0,0 +1,195 @@
+use crate::result::ExecutionResult;
+use executor_client::{BalanceChanges, OrderingOutcome, SearchStrategy, MAX_BEAM_WIDTH};
+use itertools::Itertools;
+use solana_sdk::pubkey::Pubkey;
+use std::{cmp::Ordering, collections::HashMap};
+
+/// Evaluates orderings of `len` candidates with the strategy, returning every outcome in
+/// evaluation order.
+pub fn search_orderings<F>(
+    len: usize,
+    strategy: SearchStrategy,
+    mut evaluate: F,
+) -> Vec<OrderingOutcome>
+where
+    F: FnMut(&[usize]) -> OrderingOutcome,
+{
+    if len == 0 {
+        return vec![evaluate(&[])];
+    }
+
+    match strategy {
+        SearchStrategy::Exhaustive => (0..len)
+            .permutations(len)
+            .map(|ordering| evaluate(&ordering))
+            .collect(),
+        SearchStrategy::Beam { width } => {
+            let mut outcomes = vec![];
+            let mut beam: Vec<Vec<usize>> = vec![vec![]];
+            for _ in 0..len {
+                let mut extensions = vec![];
+                for prefix in &beam {
+                    for next in (0..len).filter(|index| !prefix.contains(index)) {
+                        let ordering = [prefix.as_slice(), &[next]].concat();
+                        let outcome = evaluate(&ordering);
+                        extensions.push((ordering, outcome.profit));
+                        outcomes.push(outcome);
+                    }
+                }
+                // Stable sort, equally profitable prefixes keep their exploration order
+                extensions.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+                extensions.truncate(width.clamp(1, MAX_BEAM_WIDTH));
+                beam = extensions
+                    .into_iter()
+                    .map(|(ordering, _)| ordering)
+                    .collect();
+            }
+
+            outcomes
+        }
+    }
+}
+
+/// First of the most profitable outcomes executing all the candidates.
+pub fn best_outcome(outcomes: &[OrderingOutcome], len: usize) -> Option<&OrderingOutcome> {
+    outcomes
+        .iter()
+        .filter(|outcome| outcome.ordering.len() == len)
+        .fold(None, |best: Option<&OrderingOutcome>, outcome| match best {
+            Some(best) if best.profit >= outcome.profit => Some(best),
+            _ => Some(outcome),
+        })
+}
+
+/// Values the balance changes of `wallet` over the executed candidates.
+pub fn ordering_outcome(
+    ordering: &[usize],
+    results: &[ExecutionResult],
+    wallet: &Pubkey,
+    token_prices: &HashMap<Pubkey, f64>,
+) -> OrderingOutcome {
+    let balance_changes = BalanceChanges::aggregate(results.iter().map(|r| &r.balance_changes));
+    let sol_delta = balance_changes.sol_delta(wallet);
+    let token_deltas = balance_changes
+        .tokens
+        .into_iter()
+        .filter(|change| &change.owner == wallet)
+        .collect_vec();
+    let token_value: f64 = token_deltas
+        .iter()
+        .filter_map(|change| {
+            token_prices
+                .get(&change.mint)
+                .map(|price| change.amount_delta as f64 * price)
+        })
+        .sum();
+
+    OrderingOutcome {
+        ordering: ordering.to_vec(),
+        profit: sol_delta as f64 + token_value,
+        sol_delta,
+        token_deltas,
+        errors: results
+            .iter()
+            .map(|result| result.status.clone().err())
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WEIGHTS: [f64; 3] = [1.0, 3.0, 2.0];
+
+    /// Weights of the candidates, earlier positions counting more.
+    fn evaluate(ordering: &[usize]) -> OrderingOutcome {
+        OrderingOutcome {
+            ordering: ordering.to_vec(),
+            profit: ordering
+                .iter()
+                .enumerate()
+                .map(|(position, index)| WEIGHTS[*index] * (WEIGHTS.len() - position) as f64)
+                .sum(),
+            sol_delta: 0,
+            token_deltas: vec![],
+            errors: vec![],
+        }
+    }
+
+    fn orderings(outcomes: &[OrderingOutcome]) -> Vec<Vec<usize>> {
+        outcomes
+            .iter()
+            .map(|outcome| outcome.ordering.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_exhaustive_search() {
+        let outcomes = search_orderings(3, SearchStrategy::Exhaustive, evaluate);
+
+        assert_eq!(
+            orderings(&outcomes),
+            vec![
+                vec![0, 1, 2],
+                vec![0, 2, 1],
+                vec![1, 0, 2],
+                vec![1, 2, 0],
+                vec![2, 0, 1],
+                vec![2, 1, 0],
+            ]
+        );
+        let best = best_outcome(&outcomes, 3).unwrap();
+        assert_eq!(best.ordering, vec![1, 2, 0]);
+        assert_eq!(best.profit, 14.0);
+    }
+
+    #[test]
+    fn test_greedy_search() {
+        let outcomes = search_orderings(3, SearchStrategy::Beam { width: 1 }, evaluate);
+
+        assert_eq!(
+            orderings(&outcomes),
+            vec![
+                vec![0],
+                vec![1],
+                vec![2],
+                vec![1, 0],
+                vec![1, 2],
+                vec![1, 2, 0],
+            ]
+        );
+        assert_eq!(best_outcome(&outcomes, 3).unwrap().ordering, vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn test_beam_width_is_clamped() {
+        let zero_width = search_orderings(3, SearchStrategy::Beam { width: 0 }, evaluate);
+        let unbounded_width =
+            search_orderings(3, SearchStrategy::Beam { width: usize::MAX }, evaluate);
+
+        assert_eq!(zero_width.len(), 6);
+        // Every prefix is kept with 3 candidates: 3 + 3 * 2 + 6 * 1 orderings
+        assert_eq!(unbounded_width.len(), 15);
+        assert_eq!(
+            best_outcome(&unbounded_width, 3).unwrap().ordering,
+            vec![1, 2, 0]
+        );
+    }
+
+    #[test]
+    fn test_search_without_candidates() {
+        let outcomes = search_orderings(0, SearchStrategy::Exhaustive, evaluate);
+
+        assert_eq!(orderings(&outcomes), vec![Vec::<usize>::new()]);
+        assert!(best_outcome(&outcomes, 0).is_some());
+    }
+
+    #[test]
+    fn test_best_outcome_keeps_first_of_ties() {
+        let mut outcomes = vec![evaluate(&[0, 1]), evaluate(&[1]), evaluate(&[1, 0])];
+        outcomes[2].profit = outcomes[0].profit;
+
+        assert_eq!(best_outcome(&outcomes, 2).unwrap().ordering, vec![0, 1]);
+    }
+}
@@ -0,0 +1,81 @@
+use executor_client::{
+    ExecutionProfile, ExecutionTimings, InstructionProfile, Invocation, ProgramProfile,
+};
+use solana_program_runtime::timings::{ExecuteTimingType, ExecuteTimings};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
+
+/// Breaks the consumed compute units down per instruction and per program, using the call tree.
+pub fn build_profile(
+    compute_units_consumed: u64,
+    call_tree: &[Invocation],
+    timings: &ExecuteTimings,
+) -> ExecutionProfile {
+    let instructions: Vec<InstructionProfile> = call_tree.iter().map(profile_invocation).collect();
+
+    let mut programs = BTreeMap::new();
+    for instruction in &instructions {
+        accumulate_programs(&mut programs, instruction);
+    }
+
+    ExecutionProfile {
+        compute_units_consumed,
+        instructions,
+        programs: programs.into_values().collect(),
+        timings: ExecutionTimings {
+            check_us: timings.metrics[ExecuteTimingType::CheckUs],
+            load_us: timings.metrics[ExecuteTimingType::LoadUs],
+            execute_us: timings.metrics[ExecuteTimingType::ExecuteUs],
+            store_us: timings.metrics[ExecuteTimingType::StoreUs],
+            serialize_us: timings.details.serialize_us,
+            create_vm_us: timings.details.create_vm_us,
+            vm_execute_us: timings.details.execute_us,
+            deserialize_us: timings.details.deserialize_us,
+            get_or_create_executor_us: timings.details.get_or_create_executor_us,
+        },
+    }
+}
+
+fn profile_invocation(invocation: &Invocation) -> InstructionProfile {
+    let inner_instructions: Vec<InstructionProfile> = invocation
+        .inner_invocations
+        .iter()
+        .map(profile_invocation)
+        .collect();
+    let inner_compute_units = inner_instructions
+        .iter()
+        .map(|inner| inner.total_compute_units)
+        .sum();
+    // Builtin programs don't log their consumption, fall back to their children's
+    let total_compute_units = invocation
+        .compute_units_consumed
+        .unwrap_or(inner_compute_units)
+        .max(inner_compute_units);
+
+    InstructionProfile {
+        program_id: invocation.program_id,
+        stack_height: invocation.stack_height,
+        compute_units: total_compute_units - inner_compute_units,
+        total_compute_units,
+        inner_instructions,
+    }
+}
+
+fn accumulate_programs(
+    programs: &mut BTreeMap<Pubkey, ProgramProfile>,
+    instruction: &InstructionProfile,
+) {
+    let program = programs
+        .entry(instruction.program_id)
+        .or_insert_with(|| ProgramProfile {
+            program_id: instruction.program_id,
+            invocations: 0,
+            compute_units: 0,
+        });
+    program.invocations += 1;
+    program.compute_units += instruction.compute_units;
+
+    for inner in &instruction.inner_instructions {
+        accumulate_programs(programs, inner);
+    }
+}
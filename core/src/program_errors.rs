@@ -0,0 +1,329 @@
+use crate::programs::{SPL_ASSOCIATED_TOKEN_PID, SPL_TOKEN_PID};
+use executor_client::{DecodedError, Invocation, ProgramErrorDefinition};
+use solana_sdk::{
+    instruction::InstructionError,
+    message::Message,
+    pubkey::Pubkey,
+    transaction::{self, TransactionError},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Debug, Display},
+};
+
+type ErrorDecoder = Box<dyn Fn(u32) -> Option<ProgramErrorDefinition> + Send + Sync>;
+
+#[rustfmt::skip]
+const SPL_TOKEN_ERRORS: &[(u32, &str, &str)] = &[
+    (0, "NotRentExempt", "Lamport balance below rent-exempt threshold"),
+    (1, "InsufficientFunds", "Insufficient funds"),
+    (2, "InvalidMint", "Invalid Mint"),
+    (3, "MintMismatch", "Account not associated with this Mint"),
+    (4, "OwnerMismatch", "Owner does not match"),
+    (5, "FixedSupply", "Fixed supply"),
+    (6, "AlreadyInUse", "Already in use"),
+    (7, "InvalidNumberOfProvidedSigners", "Invalid number of provided signers"),
+    (8, "InvalidNumberOfRequiredSigners", "Invalid number of required signers"),
+    (9, "UninitializedState", "State is unititialized"),
+    (10, "NativeNotSupported", "Instruction does not support native tokens"),
+    (11, "NonNativeHasBalance", "Non-native account can only be closed if its balance is zero"),
+    (12, "InvalidInstruction", "Invalid instruction"),
+    (13, "InvalidState", "State is invalid for requested operation"),
+    (14, "Overflow", "Operation overflowed"),
+    (15, "AuthorityTypeNotSupported", "Account does not support specified authority type"),
+    (16, "MintCannotFreeze", "This token mint cannot freeze accounts"),
+    (17, "AccountFrozen", "Account is frozen"),
+    (18, "MintDecimalsMismatch", "The provided decimals value different from the Mint decimals"),
+    (19, "NonNativeNotSupported", "Instruction does not support non-native tokens"),
+];
+
+#[rustfmt::skip]
+const SPL_ASSOCIATED_TOKEN_ERRORS: &[(u32, &str, &str)] = &[
+    (0, "InvalidOwner", "Associated token account owner does not match address derivation"),
+];
+
+/// Framework error codes shared by all Anchor programs, their own errors start at 6000.
+#[rustfmt::skip]
+const ANCHOR_ERRORS: &[(u32, &str, &str)] = &[
+    (100, "InstructionMissing", "8 byte instruction identifier not provided"),
+    (101, "InstructionFallbackNotFound", "Fallback functions are not supported"),
+    (102, "InstructionDidNotDeserialize", "The program could not deserialize the given instruction"),
+    (103, "InstructionDidNotSerialize", "The program could not serialize the given instruction"),
+    (1000, "IdlInstructionStub", "The program was compiled without idl instructions"),
+    (1001, "IdlInstructionInvalidProgram", "Invalid program given to the IDL instruction"),
+    (2000, "ConstraintMut", "A mut constraint was violated"),
+    (2001, "ConstraintHasOne", "A has one constraint was violated"),
+    (2002, "ConstraintSigner", "A signer constraint was violated"),
+    (2003, "ConstraintRaw", "A raw constraint was violated"),
+    (2004, "ConstraintOwner", "An owner constraint was violated"),
+    (2005, "ConstraintRentExempt", "A rent exemption constraint was violated"),
+    (2006, "ConstraintSeeds", "A seeds constraint was violated"),
+    (2007, "ConstraintExecutable", "An executable constraint was violated"),
+    (2008, "ConstraintState", "A state constraint was violated"),
+    (2009, "ConstraintAssociated", "An associated constraint was violated"),
+    (2010, "ConstraintAssociatedInit", "An associated init constraint was violated"),
+    (2011, "ConstraintClose", "A close constraint was violated"),
+    (2012, "ConstraintAddress", "An address constraint was violated"),
+    (2013, "ConstraintZero", "Expected zero account discriminant"),
+    (2014, "ConstraintTokenMint", "A token mint constraint was violated"),
+    (2015, "ConstraintTokenOwner", "A token owner constraint was violated"),
+    (2016, "ConstraintMintMintAuthority", "A mint mint authority constraint was violated"),
+    (2017, "ConstraintMintFreezeAuthority", "A mint freeze authority constraint was violated"),
+    (2018, "ConstraintMintDecimals", "A mint decimals constraint was violated"),
+    (2019, "ConstraintSpace", "A space constraint was violated"),
+    (2500, "RequireViolated", "A require expression was violated"),
+    (2501, "RequireEqViolated", "A require_eq expression was violated"),
+    (2502, "RequireKeysEqViolated", "A require_keys_eq expression was violated"),
+    (2503, "RequireNeqViolated", "A require_neq expression was violated"),
+    (2504, "RequireKeysNeqViolated", "A require_keys_neq expression was violated"),
+    (2505, "RequireGtViolated", "A require_gt expression was violated"),
+    (2506, "RequireGteViolated", "A require_gte expression was violated"),
+    (3000, "AccountDiscriminatorAlreadySet", "The account discriminator was already set on this account"),
+    (3001, "AccountDiscriminatorNotFound", "No 8 byte discriminator was found on the account"),
+    (3002, "AccountDiscriminatorMismatch", "8 byte discriminator did not match what was expected"),
+    (3003, "AccountDidNotDeserialize", "Failed to deserialize the account"),
+    (3004, "AccountDidNotSerialize", "Failed to serialize the account"),
+    (3005, "AccountNotEnoughKeys", "Not enough account keys given to the instruction"),
+    (3006, "AccountNotMutable", "The given account is not mutable"),
+    (3007, "AccountOwnedByWrongProgram", "The given account is owned by a different program than expected"),
+    (3008, "InvalidProgramId", "Program ID was not as expected"),
+    (3009, "InvalidProgramExecutable", "Program account is not executable"),
+    (3010, "AccountNotSigner", "The given account did not sign"),
+    (3011, "AccountNotSystemOwned", "The given account is not owned by the system program"),
+    (3012, "AccountNotInitialized", "The program expected this account to be already initialized"),
+    (3013, "AccountNotProgramData", "The given account is not a program data account"),
+    (3014, "AccountNotAssociatedTokenAccount", "The given account is not the associated token account"),
+    (3015, "AccountSysvarMismatch", "The given public key does not match the required sysvar"),
+    (3016, "AccountReallocExceedsLimit", "The account reallocation exceeds the MAX_PERMITTED_DATA_INCREASE limit"),
+    (3017, "AccountDuplicateReallocs", "The account was duplicated for more than one reallocation"),
+    (4000, "StateInvalidAddress", "The given state account does not have the correct address"),
+    (4100, "DeclaredProgramIdMismatch", "The declared program id does not match the actual program id"),
+    (5000, "Deprecated", "The API being used is deprecated and should no longer be used"),
+];
+
+/// Custom error codes of known programs, by program id. The bundled SPL programs are always
+/// known (the memo programs only return builtin errors), Anchor framework codes are tried for
+/// the programs registered as Anchor ones. Errors registered by the user take precedence over
+/// the ones declared by an IDL.
+#[derive(Default)]
+pub struct ProgramErrorRegistry {
+    decoders: HashMap<Pubkey, ErrorDecoder>,
+    idl_errors: HashMap<Pubkey, HashMap<u32, ProgramErrorDefinition>>,
+    anchor_programs: HashSet<Pubkey>,
+}
+
+impl ProgramErrorRegistry {
+    pub fn register_errors(&mut self, program_id: Pubkey, errors: Vec<ProgramErrorDefinition>) {
+        let errors: HashMap<u32, ProgramErrorDefinition> = errors
+            .into_iter()
+            .map(|error| (error.code, error))
+            .collect();
+        self.decoders
+            .insert(program_id, Box::new(move |code| errors.get(&code).cloned()));
+    }
+
+    /// Registers a program's error enum through its code conversion, typically
+    /// `FromPrimitive::from_u32`. Variant names are taken from `Debug`, messages from `Display`.
+    pub fn register_error_enum<E: Debug + Display + 'static>(
+        &mut self,
+        program_id: Pubkey,
+        from_code: fn(u32) -> Option<E>,
+    ) {
+        self.decoders.insert(
+            program_id,
+            Box::new(move |code| {
+                from_code(code).map(|error| ProgramErrorDefinition {
+                    code,
+                    name: format!("{:?}", error),
+                    message: error.to_string(),
+                })
+            }),
+        );
+    }
+
+    /// Registers the errors declared by a program's IDL, replacing the ones of a previous IDL.
+    pub fn register_idl_errors(&mut self, program_id: Pubkey, errors: Vec<ProgramErrorDefinition>) {
+        self.idl_errors.insert(
+            program_id,
+            errors
+                .into_iter()
+                .map(|error| (error.code, error))
+                .collect(),
+        );
+    }
+
+    /// Names the Anchor framework error codes of the program, typically one with an IDL.
+    pub fn register_anchor_program(&mut self, program_id: Pubkey) {
+        self.anchor_programs.insert(program_id);
+    }
+
+    /// Decodes the instruction error of a failed transaction. The error is attributed to the
+    /// innermost failed invocation, since custom errors are propagated unchanged through CPIs.
+    pub fn decode(
+        &self,
+        status: &transaction::Result<()>,
+        message: &Message,
+        call_tree: &[Invocation],
+    ) -> Option<DecodedError> {
+        let (instruction_index, error) = match status {
+            Err(TransactionError::InstructionError(index, error)) => (*index, error),
+            _ => return None,
+        };
+        let program_id = call_tree
+            .iter()
+            .find_map(innermost_failed_invocation)
+            .map(|invocation| invocation.program_id)
+            .or_else(|| {
+                message
+                    .instructions
+                    .get(instruction_index as usize)
+                    .map(|ix| message.account_keys[ix.program_id_index as usize])
+            })?;
+
+        let (code, definition) = match error {
+            InstructionError::Custom(code) => (Some(*code), self.lookup(&program_id, *code)),
+            _ => (None, None),
+        };
+
+        Some(match definition {
+            Some(definition) => DecodedError {
+                instruction_index,
+                program_id,
+                code,
+                name: Some(definition.name),
+                message: definition.message,
+            },
+            None => DecodedError {
+                instruction_index,
+                program_id,
+                code,
+                name: code.is_none().then(|| format!("{:?}", error)),
+                message: error.to_string(),
+            },
+        })
+    }
+
+    fn lookup(&self, program_id: &Pubkey, code: u32) -> Option<ProgramErrorDefinition> {
+        if let Some(definition) = self
+            .decoders
+            .get(program_id)
+            .and_then(|decoder| decoder(code))
+        {
+            return Some(definition);
+        }
+        if let Some(definition) = self
+            .idl_errors
+            .get(program_id)
+            .and_then(|errors| errors.get(&code))
+        {
+            return Some(definition.clone());
+        }
+
+        let table = if *program_id == SPL_TOKEN_PID {
+            SPL_TOKEN_ERRORS
+        } else if *program_id == SPL_ASSOCIATED_TOKEN_PID {
+            SPL_ASSOCIATED_TOKEN_ERRORS
+        } else if self.anchor_programs.contains(program_id) {
+            ANCHOR_ERRORS
+        } else {
+            return None;
+        };
+        table
+            .iter()
+            .find(|(c, _, _)| *c == code)
+            .map(|(code, name, message)| ProgramErrorDefinition {
+                code: *code,
+                name: name.to_string(),
+                message: message.to_string(),
+            })
+    }
+}
+
+fn innermost_failed_invocation(invocation: &Invocation) -> Option<&Invocation> {
+    invocation.error.as_ref()?;
+    Some(
+        invocation
+            .inner_invocations
+            .iter()
+            .find_map(innermost_failed_invocation)
+            .unwrap_or(invocation),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::Instruction;
+
+    fn definition(code: u32, name: &str) -> ProgramErrorDefinition {
+        ProgramErrorDefinition {
+            code,
+            name: name.to_string(),
+            message: format!("{} message", name),
+        }
+    }
+
+    fn decode_name(
+        registry: &ProgramErrorRegistry,
+        program_id: Pubkey,
+        code: u32,
+    ) -> Option<String> {
+        let message = Message::new(
+            &[Instruction::new_with_bytes(program_id, &[], vec![])],
+            None,
+        );
+        let status = Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code),
+        ));
+        registry.decode(&status, &message, &[])?.name
+    }
+
+    #[test]
+    fn test_user_errors_take_precedence_over_idl_errors() {
+        let program_id = Pubkey::new_unique();
+        let mut registry = ProgramErrorRegistry::default();
+        registry.register_errors(program_id, vec![definition(6000, "UserError")]);
+        registry.register_idl_errors(
+            program_id,
+            vec![
+                definition(6000, "IdlError"),
+                definition(6001, "OtherIdlError"),
+            ],
+        );
+
+        assert_eq!(
+            decode_name(&registry, program_id, 6000).as_deref(),
+            Some("UserError")
+        );
+        assert_eq!(
+            decode_name(&registry, program_id, 6001).as_deref(),
+            Some("OtherIdlError")
+        );
+
+        // Registering in the other order keeps the user error too.
+        registry.register_idl_errors(program_id, vec![definition(6000, "NewIdlError")]);
+        assert_eq!(
+            decode_name(&registry, program_id, 6000).as_deref(),
+            Some("UserError")
+        );
+        assert_eq!(decode_name(&registry, program_id, 6001), None);
+    }
+
+    #[test]
+    fn test_anchor_errors_only_for_anchor_programs() {
+        let program_id = Pubkey::new_unique();
+        let mut registry = ProgramErrorRegistry::default();
+        assert_eq!(decode_name(&registry, program_id, 2000), None);
+
+        registry.register_anchor_program(program_id);
+        assert_eq!(
+            decode_name(&registry, program_id, 2000).as_deref(),
+            Some("ConstraintMut")
+        );
+        assert_eq!(
+            decode_name(&registry, SPL_TOKEN_PID, 1).as_deref(),
+            Some("InsufficientFunds")
+        );
+    }
+}
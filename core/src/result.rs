@@ -0,0 +1,218 @@
+use executor_client::{
+    AccountDiff, BalanceChanges, BatchProfile, BatchSummary, BundleSimulation, BundleViolation,
+    DecodedError, EncodedExecutionResult, EncodedSealedBlock, EncodingConfig, ExecutionProfile,
+    FeeBreakdown, Invocation, ScheduleAnalysis, ScheduleComparison, ScheduleReport,
+    SimulatedTransaction,
+};
+use solana_program_runtime::timings::ExecuteTimings;
+use solana_sdk::{
+    account::Account,
+    clock::{Slot, UnixTimestamp},
+    message::v0::LoadedAddresses,
+    pubkey::Pubkey,
+    transaction::{self, VersionedTransaction},
+    transaction_context::TransactionReturnData,
+};
+use solana_transaction_status::{
+    ConfirmedTransactionWithStatusMeta, InnerInstructions, TransactionStatusMeta,
+    TransactionTokenBalance, TransactionWithStatusMeta, UiTransactionEncoding,
+    VersionedTransactionWithStatusMeta,
+};
+
+/// Typed outcome of a transaction execution, encoded only when leaving the process.
+pub struct ExecutionResult {
+    pub slot: Slot,
+    pub block_time: Option<UnixTimestamp>,
+    pub transaction: VersionedTransaction,
+    /// Whether the bank executed the transaction and charged its fee, even if it failed. Those
+    /// failing to load, e.g. on `BlockhashNotFound`, leave the bank untouched.
+    pub executed: bool,
+    pub status: transaction::Result<()>,
+    pub fee: u64,
+    /// Fee a leader would charge, including the prioritization fee.
+    pub fee_breakdown: FeeBreakdown,
+    /// Net SOL change per account and token change per (owner, mint).
+    pub balance_changes: BalanceChanges,
+    pub pre_balances: Vec<u64>,
+    pub post_balances: Vec<u64>,
+    pub pre_token_balances: Vec<TransactionTokenBalance>,
+    pub post_token_balances: Vec<TransactionTokenBalance>,
+    pub inner_instructions: Option<Vec<InnerInstructions>>,
+    pub log_messages: Option<Vec<String>>,
+    pub return_data: Option<TransactionReturnData>,
+    pub compute_units_consumed: u64,
+    /// Writable accounts whose state changed: lamports, owner and changed data ranges.
+    pub account_diffs: Vec<AccountDiff>,
+    /// Top-level invocations, reconstructed from the logs.
+    pub call_tree: Vec<Invocation>,
+    /// Compute units per instruction and per program, along with the bank's timings.
+    pub profile: ExecutionProfile,
+    /// Instruction error of a failed transaction, named when the program's errors are known.
+    pub decoded_error: Option<DecodedError>,
+    pub timings: ExecuteTimings,
+}
+
+impl ExecutionResult {
+    pub fn status_meta(&self) -> TransactionStatusMeta {
+        TransactionStatusMeta {
+            status: self.status.clone(),
+            fee: self.fee,
+            pre_balances: self.pre_balances.clone(),
+            post_balances: self.post_balances.clone(),
+            pre_token_balances: Some(self.pre_token_balances.clone()),
+            post_token_balances: Some(self.post_token_balances.clone()),
+            inner_instructions: self.inner_instructions.clone(),
+            log_messages: self.log_messages.clone(),
+            rewards: None,
+            loaded_addresses: LoadedAddresses {
+                writable: vec![], // TODO
+                readonly: vec![], // TODO
+            },
+            return_data: self.return_data.clone(),
+            compute_units_consumed: Some(self.compute_units_consumed),
+        }
+    }
+
+    pub fn to_confirmed_transaction(&self) -> ConfirmedTransactionWithStatusMeta {
+        ConfirmedTransactionWithStatusMeta {
+            slot: self.slot,
+            tx_with_meta: TransactionWithStatusMeta::Complete(VersionedTransactionWithStatusMeta {
+                transaction: self.transaction.clone(),
+                meta: self.status_meta(),
+            }),
+            block_time: self.block_time,
+        }
+    }
+
+    pub fn encode(&self, config: &EncodingConfig) -> EncodedExecutionResult {
+        EncodedExecutionResult {
+            transaction: self
+                .to_confirmed_transaction()
+                .encode(
+                    config.encoding.unwrap_or(UiTransactionEncoding::Binary),
+                    None,
+                )
+                .expect("Failed to encode transaction"),
+            account_diffs: config.account_diffs.then(|| self.account_diffs.clone()),
+            call_tree: self.call_tree.clone(),
+            profile: self.profile.clone(),
+            fee_breakdown: self.fee_breakdown.clone(),
+            balance_changes: self.balance_changes.clone(),
+            decoded_error: self.decoded_error.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl ExecutionResult {
+    /// Successful execution of the transaction, without any effect.
+    pub(crate) fn for_tests(transaction: VersionedTransaction, slot: Slot) -> Self {
+        ExecutionResult {
+            slot,
+            block_time: None,
+            transaction,
+            executed: true,
+            status: Ok(()),
+            fee: 5000,
+            fee_breakdown: FeeBreakdown::default(),
+            balance_changes: BalanceChanges::default(),
+            pre_balances: vec![],
+            post_balances: vec![],
+            pre_token_balances: vec![],
+            post_token_balances: vec![],
+            inner_instructions: None,
+            log_messages: None,
+            return_data: None,
+            compute_units_consumed: 0,
+            account_diffs: vec![],
+            call_tree: vec![],
+            profile: ExecutionProfile::default(),
+            decoded_error: None,
+            timings: ExecuteTimings::default(),
+        }
+    }
+}
+
+/// Execution result of a simulated transaction, along with the state of its accounts afterwards.
+pub struct SimulationResult {
+    pub result: ExecutionResult,
+    pub post_accounts: Vec<(Pubkey, Option<Account>)>,
+}
+
+impl SimulationResult {
+    pub fn encode(&self, config: &EncodingConfig) -> SimulatedTransaction {
+        SimulatedTransaction {
+            transaction: self.result.encode(config),
+            post_accounts: self.post_accounts.clone(),
+        }
+    }
+}
+
+/// Aggregates the profiles and balance changes of a batch's results.
+pub fn summarize_batch<'a>(results: impl IntoIterator<Item = &'a ExecutionResult>) -> BatchSummary {
+    let results: Vec<&ExecutionResult> = results.into_iter().collect();
+    BatchSummary {
+        profile: BatchProfile::aggregate(results.iter().map(|result| &result.profile)),
+        balance_changes: BalanceChanges::aggregate(
+            results.iter().map(|result| &result.balance_changes),
+        ),
+    }
+}
+
+/// Pending transactions executed in a block, in the sealed order.
+pub struct SealedBlock {
+    pub slot: Slot,
+    pub results: Vec<ExecutionResult>,
+}
+
+impl SealedBlock {
+    pub fn encode(&self, config: &EncodingConfig) -> EncodedSealedBlock {
+        EncodedSealedBlock {
+            slot: self.slot,
+            results: self
+                .results
+                .iter()
+                .map(|result| result.encode(config))
+                .collect(),
+        }
+    }
+}
+
+/// Simulated bundle, along with the tip it pays and the bundle rules it breaks.
+pub struct BundleSimulationResult {
+    pub results: Vec<SimulationResult>,
+    pub tip_lamports: u64,
+    pub violations: Vec<BundleViolation>,
+}
+
+impl BundleSimulationResult {
+    pub fn encode(&self, config: &EncodingConfig) -> BundleSimulation {
+        BundleSimulation {
+            transactions: self
+                .results
+                .iter()
+                .map(|result| result.encode(config))
+                .collect(),
+            tip_lamports: self.tip_lamports,
+            violations: self.violations.clone(),
+        }
+    }
+}
+
+/// Batch executed by parallel groups and sequentially, each on a fork of the working bank.
+pub struct ScheduleSimulationResult {
+    pub analysis: ScheduleAnalysis,
+    /// Results of the group executions, in batch order.
+    pub parallel_results: Vec<ExecutionResult>,
+    pub sequential_results: Vec<ExecutionResult>,
+    pub comparison: ScheduleComparison,
+}
+
+impl ScheduleSimulationResult {
+    pub fn report(&self) -> ScheduleReport {
+        ScheduleReport {
+            analysis: self.analysis.clone(),
+            comparison: Some(self.comparison.clone()),
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use executor_client::{AccountLocks, LockConflict, ScheduleAnalysis};
+use itertools::Itertools;
+use solana_sdk::{pubkey::Pubkey, transaction::Transaction};
+
+/// Accounts the transaction locks, program ids and sysvars being demoted to read-only.
+pub fn account_locks(tx: &Transaction) -> AccountLocks {
+    let (writable, readonly) = tx
+        .message
+        .account_keys
+        .iter()
+        .enumerate()
+        .partition::<Vec<_>, _>(|(index, _)| tx.message.is_writable(*index));
+
+    AccountLocks {
+        writable: writable.into_iter().map(|(_, pubkey)| *pubkey).collect(),
+        readonly: readonly.into_iter().map(|(_, pubkey)| *pubkey).collect(),
+    }
+}
+
+/// Accounts one transaction writes to while the other one locks them.
+fn conflicting_accounts(first: &AccountLocks, second: &AccountLocks) -> Vec<Pubkey> {
+    let locked = |locks: &AccountLocks, pubkey: &Pubkey| {
+        locks.writable.contains(pubkey) || locks.readonly.contains(pubkey)
+    };
+    first
+        .writable
+        .iter()
+        .filter(|pubkey| locked(second, pubkey))
+        .chain(
+            second
+                .writable
+                .iter()
+                .filter(|pubkey| first.readonly.contains(pubkey)),
+        )
+        .unique()
+        .copied()
+        .collect()
+}
+
+/// Builds the conflict graph of the batch and schedules each transaction in the group
+/// following the last one holding a conflicting transaction.
+pub fn analyze_schedule(batch: &[Transaction]) -> ScheduleAnalysis {
+    let locks = batch.iter().map(account_locks).collect_vec();
+
+    let conflicts = (0..batch.len())
+        .tuple_combinations()
+        .filter_map(|(first, second)| {
+            let accounts = conflicting_accounts(&locks[first], &locks[second]);
+            (!accounts.is_empty()).then(|| LockConflict {
+                first,
+                second,
+                accounts,
+            })
+        })
+        .collect_vec();
+
+    let mut levels = vec![0; batch.len()];
+    for conflict in &conflicts {
+        // Conflicts are sorted by their first transaction, whose level is final by now
+        levels[conflict.second] = levels[conflict.second].max(levels[conflict.first] + 1);
+    }
+    let mut groups: Vec<Vec<usize>> = vec![];
+    for (index, level) in levels.into_iter().enumerate() {
+        if groups.len() <= level {
+            groups.resize(level + 1, vec![]);
+        }
+        groups[level].push(index);
+    }
+
+    ScheduleAnalysis {
+        locks,
+        conflicts,
+        groups,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        system_instruction, system_program,
+    };
+
+    fn transfer(from: &Pubkey, to: &Pubkey) -> Transaction {
+        Transaction::new_with_payer(&[system_instruction::transfer(from, to, 1)], Some(from))
+    }
+
+    #[test]
+    fn test_account_locks() {
+        let from = Pubkey::new_unique();
+        let to = Pubkey::new_unique();
+
+        let locks = account_locks(&transfer(&from, &to));
+
+        assert_eq!(locks.writable, vec![from, to]);
+        assert_eq!(locks.readonly, vec![system_program::ID]);
+    }
+
+    #[test]
+    fn test_analyze_schedule_groups_conflicting_transactions() {
+        let keys: Vec<Pubkey> = (0..8).map(|_| Pubkey::new_unique()).collect();
+        let batch = vec![
+            transfer(&keys[0], &keys[1]),
+            transfer(&keys[2], &keys[3]),
+            transfer(&keys[1], &keys[4]),
+            transfer(&keys[5], &keys[6]),
+            transfer(&keys[4], &keys[2]),
+        ];
+
+        let analysis = analyze_schedule(&batch);
+
+        let conflicts: Vec<(usize, usize, Vec<Pubkey>)> = analysis
+            .conflicts
+            .iter()
+            .map(|conflict| (conflict.first, conflict.second, conflict.accounts.clone()))
+            .collect();
+        assert_eq!(
+            conflicts,
+            vec![
+                (0, 2, vec![keys[1]]),
+                (1, 4, vec![keys[2]]),
+                (2, 4, vec![keys[4]]),
+            ]
+        );
+        assert_eq!(analysis.groups, vec![vec![0, 1, 3], vec![2], vec![4]]);
+        assert_eq!(analysis.locks.len(), batch.len());
+    }
+
+    #[test]
+    fn test_analyze_schedule_read_write_conflicts() {
+        let payer = Pubkey::new_unique();
+        let account = Pubkey::new_unique();
+        let read = |payer: &Pubkey| {
+            Transaction::new_with_payer(
+                &[Instruction::new_with_bytes(
+                    Pubkey::new_unique(),
+                    &[],
+                    vec![AccountMeta::new_readonly(account, false)],
+                )],
+                Some(payer),
+            )
+        };
+        let batch = vec![
+            read(&Pubkey::new_unique()),
+            read(&Pubkey::new_unique()),
+            transfer(&payer, &account),
+            read(&Pubkey::new_unique()),
+        ];
+
+        let analysis = analyze_schedule(&batch);
+
+        let conflicts: Vec<(usize, usize)> = analysis
+            .conflicts
+            .iter()
+            .map(|conflict| (conflict.first, conflict.second))
+            .collect();
+        assert_eq!(conflicts, vec![(0, 2), (1, 2), (2, 3)]);
+        assert_eq!(analysis.groups, vec![vec![0, 1], vec![2], vec![3]]);
+    }
+
+    #[test]
+    fn test_analyze_empty_batch() {
+        let analysis = analyze_schedule(&[]);
+
+        assert!(analysis.conflicts.is_empty());
+        assert!(analysis.groups.is_empty());
+    }
+}
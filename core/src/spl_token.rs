@@ -0,0 +1,79 @@
+use solana_sdk::pubkey::Pubkey;
+
+// SPL Token account layout
+pub const TOKEN_ACCOUNT_LEN: usize = 165;
+pub const TOKEN_ACCOUNT_MINT_OFFSET: usize = 0;
+pub const TOKEN_ACCOUNT_OWNER_OFFSET: usize = 32;
+pub const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+/// `COption<Pubkey>`, a `u32` tag followed by the delegate.
+pub const TOKEN_ACCOUNT_DELEGATE_OFFSET: usize = 72;
+/// `AccountState`: uninitialized, initialized or frozen.
+pub const TOKEN_ACCOUNT_STATE_OFFSET: usize = 108;
+const TOKEN_ACCOUNT_UNINITIALIZED: u8 = 0;
+
+// SPL Token mint layout, `decimals` follows the `COption<Pubkey>` authority and the supply
+pub const MINT_DECIMALS_OFFSET: usize = 44;
+
+/// Mint, owner and amount of an initialized SPL Token account.
+pub fn unpack_token_account(data: &[u8]) -> Option<(Pubkey, Pubkey, u64)> {
+    if data.len() != TOKEN_ACCOUNT_LEN
+        || data[TOKEN_ACCOUNT_STATE_OFFSET] == TOKEN_ACCOUNT_UNINITIALIZED
+    {
+        return None;
+    }
+    let pubkey_at = |offset: usize| Pubkey::new(&data[offset..offset + 32]);
+    let amount = u64::from_le_bytes(
+        data[TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8]
+            .try_into()
+            .unwrap(),
+    );
+    Some((
+        pubkey_at(TOKEN_ACCOUNT_MINT_OFFSET),
+        pubkey_at(TOKEN_ACCOUNT_OWNER_OFFSET),
+        amount,
+    ))
+}
+
+/// Bytes a set delegate is serialized to, for `memcmp` filters.
+pub fn delegate_filter_bytes(delegate: &Pubkey) -> Vec<u8> {
+    [&1u32.to_le_bytes()[..], delegate.as_ref()].concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_account(mint: &Pubkey, owner: &Pubkey, amount: u64, state: u8) -> Vec<u8> {
+        let mut data = vec![0; TOKEN_ACCOUNT_LEN];
+        data[TOKEN_ACCOUNT_MINT_OFFSET..TOKEN_ACCOUNT_MINT_OFFSET + 32]
+            .copy_from_slice(mint.as_ref());
+        data[TOKEN_ACCOUNT_OWNER_OFFSET..TOKEN_ACCOUNT_OWNER_OFFSET + 32]
+            .copy_from_slice(owner.as_ref());
+        data[TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8]
+            .copy_from_slice(&amount.to_le_bytes());
+        data[TOKEN_ACCOUNT_STATE_OFFSET] = state;
+        data
+    }
+
+    #[test]
+    fn test_unpack_token_account() {
+        let mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+
+        // Initialized and frozen accounts
+        for state in [1, 2] {
+            assert_eq!(
+                unpack_token_account(&token_account(&mint, &owner, 42, state)),
+                Some((mint, owner, 42))
+            );
+        }
+        assert_eq!(
+            unpack_token_account(&token_account(&mint, &owner, 42, 0)),
+            None
+        );
+        assert_eq!(
+            unpack_token_account(&token_account(&mint, &owner, 42, 1)[..TOKEN_ACCOUNT_LEN - 1]),
+            None
+        );
+    }
+}
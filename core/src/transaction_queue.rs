@@ -0,0 +1,197 @@
+use crate::compute_budget::{compute_budget_with_overrides, prioritization_fee};
+use anyhow::bail;
+use executor_client::OrderingPolicy;
+use itertools::Itertools;
+use solana_sdk::{signature::Signature, transaction::Transaction};
+use std::cmp::{Ordering, Reverse};
+
+/// Transactions submitted for the next sealed block, in submission order.
+#[derive(Default)]
+pub struct TransactionQueue {
+    transactions: Vec<Transaction>,
+}
+
+impl TransactionQueue {
+    /// Queues the transaction, unless one with the same signature is already pending.
+    pub fn submit(&mut self, tx: Transaction) -> anyhow::Result<Signature> {
+        let signature = match tx.signatures.first() {
+            Some(signature) => *signature,
+            None => bail!("Transaction is not signed"),
+        };
+        if !self.contains(&signature) {
+            self.transactions.push(tx);
+        }
+        Ok(signature)
+    }
+
+    pub fn pending(&self) -> &[Transaction] {
+        &self.transactions
+    }
+
+    /// Removes the transactions to seal, ordered by the policy. With an explicit order, the
+    /// transactions that aren't listed stay pending.
+    pub fn take_ordered(&mut self, ordering: &OrderingPolicy) -> anyhow::Result<Vec<Transaction>> {
+        match ordering {
+            OrderingPolicy::Fifo => Ok(std::mem::take(&mut self.transactions)),
+            OrderingPolicy::PriorityFee => {
+                // Stable sort, transactions paying the same price keep their submission order
+                let mut transactions = std::mem::take(&mut self.transactions);
+                transactions.sort_by_cached_key(|tx| Reverse(compute_unit_price(tx)));
+                Ok(transactions)
+            }
+            OrderingPolicy::Explicit(signatures) => {
+                if let Some(signature) = signatures.iter().duplicates().next() {
+                    bail!("Transaction {} is listed more than once", signature);
+                }
+                if let Some(signature) = signatures.iter().find(|sig| !self.contains(sig)) {
+                    bail!("Transaction {} is not pending", signature);
+                }
+
+                let (mut selected, remaining): (Vec<_>, Vec<_>) =
+                    std::mem::take(&mut self.transactions)
+                        .into_iter()
+                        .partition(|tx| {
+                            tx.signatures
+                                .first()
+                                .map_or(false, |signature| signatures.contains(signature))
+                        });
+                self.transactions = remaining;
+                selected.sort_by_key(|tx| {
+                    signatures
+                        .iter()
+                        .position(|signature| tx.signatures.first() == Some(signature))
+                });
+                Ok(selected)
+            }
+        }
+    }
+
+    /// Removes all the pending transactions, ordered by `compare`.
+    pub fn take_sorted_by<F>(&mut self, compare: F) -> Vec<Transaction>
+    where
+        F: FnMut(&Transaction, &Transaction) -> Ordering,
+    {
+        let mut transactions = std::mem::take(&mut self.transactions);
+        transactions.sort_by(compare);
+        transactions
+    }
+
+    fn contains(&self, signature: &Signature) -> bool {
+        self.transactions
+            .iter()
+            .any(|tx| tx.signatures.first() == Some(signature))
+    }
+}
+
+/// Compute unit price the transaction bids, in micro-lamports.
+pub fn compute_unit_price(tx: &Transaction) -> u64 {
+    let compute_unit_limit = compute_budget_with_overrides(&tx.message, &Default::default())
+        .map_or(0, |compute_budget| compute_budget.compute_unit_limit);
+    prioritization_fee(&tx.message, compute_unit_limit).1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{
+        compute_budget::ComputeBudgetInstruction,
+        hash::Hash,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        system_instruction,
+    };
+
+    fn transaction(compute_unit_price: Option<u64>) -> Transaction {
+        let payer = Keypair::new();
+        let mut instructions = vec![system_instruction::transfer(
+            &payer.pubkey(),
+            &Pubkey::new_unique(),
+            1,
+        )];
+        if let Some(price) = compute_unit_price {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+        }
+        Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::default(),
+        )
+    }
+
+    fn queue(transactions: &[Transaction]) -> TransactionQueue {
+        let mut queue = TransactionQueue::default();
+        for tx in transactions {
+            queue.submit(tx.clone()).unwrap();
+        }
+        queue
+    }
+
+    fn signatures(transactions: &[Transaction]) -> Vec<Signature> {
+        transactions.iter().map(|tx| tx.signatures[0]).collect()
+    }
+
+    #[test]
+    fn test_submit_skips_duplicates() {
+        let tx = transaction(None);
+        let mut queue = queue(&[tx.clone()]);
+        assert_eq!(queue.submit(tx.clone()).unwrap(), tx.signatures[0]);
+        assert_eq!(queue.pending().len(), 1);
+        assert!(queue.submit(Transaction::default()).is_err());
+    }
+
+    #[test]
+    fn test_fifo_ordering() {
+        let transactions = vec![
+            transaction(Some(1)),
+            transaction(None),
+            transaction(Some(5)),
+        ];
+        let mut queue = queue(&transactions);
+        let ordered = queue.take_ordered(&OrderingPolicy::Fifo).unwrap();
+        assert_eq!(signatures(&ordered), signatures(&transactions));
+        assert!(queue.pending().is_empty());
+    }
+
+    #[test]
+    fn test_priority_fee_ordering_is_stable() {
+        let transactions = vec![
+            transaction(None),
+            transaction(Some(10)),
+            transaction(Some(1)),
+            transaction(Some(10)),
+        ];
+        let mut queue = queue(&transactions);
+        let ordered = queue.take_ordered(&OrderingPolicy::PriorityFee).unwrap();
+        assert_eq!(
+            signatures(&ordered),
+            signatures(&[
+                transactions[1].clone(),
+                transactions[3].clone(),
+                transactions[2].clone(),
+                transactions[0].clone(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_explicit_ordering() {
+        let transactions = vec![transaction(None), transaction(None), transaction(None)];
+        let mut queue = queue(&transactions);
+        let [first, second, third] = [0, 1, 2].map(|i| transactions[i].signatures[0]);
+
+        assert!(queue
+            .take_ordered(&OrderingPolicy::Explicit(vec![first, first]))
+            .is_err());
+        assert!(queue
+            .take_ordered(&OrderingPolicy::Explicit(vec![Signature::new_unique()]))
+            .is_err());
+        assert_eq!(queue.pending().len(), 3);
+
+        let ordered = queue
+            .take_ordered(&OrderingPolicy::Explicit(vec![third, first]))
+            .unwrap();
+        assert_eq!(signatures(&ordered), vec![third, first]);
+        assert_eq!(signatures(queue.pending()), vec![second]);
+    }
+}
@@ -1,5 +1,6 @@
 use rand::rngs::OsRng;
-use solana_sdk::signature::Keypair;
+use solana_sdk::{clock::UnixTimestamp, signature::Keypair};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Clone the given keypair.
 pub fn clone_keypair(keypair: &Keypair) -> Keypair {
@@ -10,3 +11,13 @@ pub fn clone_keypair(keypair: &Keypair) -> Keypair {
 pub fn random_keypair() -> Keypair {
     Keypair::generate(&mut OsRng::default())
 }
+
+/// Current wall-clock time, as stamped on executed transactions and produced blocks.
+pub fn unix_timestamp_now() -> UnixTimestamp {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        .try_into()
+        .unwrap()
+}
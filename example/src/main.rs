@@ -1,6 +1,8 @@
 executor_client_gen::generate_client!();
 
-use executor_client::{ExecutorClient, ExecutorClientConfig};
+use executor_client::{
+    EncodedExecutionResult, EncodingConfig, ExecutorClient, ExecutorClientConfig,
+};
 use reqwest::Url;
 use solana_sdk::{
     commitment_config::CommitmentLevel,
@@ -9,7 +11,7 @@ use solana_sdk::{
     system_instruction, system_program,
     transaction::Transaction,
 };
-use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use solana_transaction_status::UiTransactionEncoding;
 
 fn main() {
     let client = ExecutorClient::default();
@@ -35,11 +37,20 @@ fn main() {
         transactions.push(transaction)
     }
 
-    let results: Vec<EncodedConfirmedTransactionWithStatusMeta> =
-        client.execute_transaction_batch(transactions).unwrap();
+    let results: Vec<EncodedExecutionResult> = client
+        .execute_transaction_batch_with_config(
+            transactions,
+            EncodingConfig {
+                encoding: Some(UiTransactionEncoding::JsonParsed),
+                account_diffs: false,
+            },
+        )
+        .unwrap()
+        .results;
 
     for result in results {
-        let meta = result.transaction.meta.unwrap();
+        let instructions = result.parsed_instructions();
+        let meta = result.transaction.transaction.meta.unwrap();
         if let Some(error) = meta.err {
             println!("Error: {:#?}", error);
             let logs: Option<Vec<String>> = meta.log_messages.into();
@@ -53,6 +64,7 @@ fn main() {
         }
 
         println!("Success");
+        println!("{:#?}", instructions);
         println!("{:#?}", &meta.pre_balances);
         println!("{:#?}", &meta.post_balances);
         let logs: Option<Vec<String>> = meta.log_messages.into();
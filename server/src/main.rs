@@ -1,17 +1,28 @@
-use executor_core::executor::{Executor, ExecutorConfig};
+use executor_core::{
+    executor::{Executor, ExecutorConfig},
+    result::ExecutionResult,
+};
+use pubsub::Notification;
 pub use solana_client::client_error::reqwest::Url;
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use solana_sdk::signature::Signature;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::{broadcast, Mutex};
 use warp::Filter;
 
+mod pubsub;
+mod rpc;
+
 pub struct ContextRaw {
     pub executor: Executor,
+    /// State changes streamed to the WebSocket subscribers.
+    pub notifications: broadcast::Sender<Notification>,
 }
 
 impl ContextRaw {
     pub fn new(config: ExecutorConfig) -> Self {
         Self {
             executor: Executor::new_with_config(config),
+            notifications: broadcast::channel(pubsub::NOTIFICATION_CHANNEL_CAPACITY).0,
         }
     }
 }
@@ -22,29 +33,82 @@ pub type Context = Arc<Mutex<ContextRaw>>;
 pub async fn main() {
     let context = Arc::new(Mutex::new(ContextRaw::new(ExecutorConfig::default())));
 
-    let api = filters::api(context);
+    let api = filters::api(context.clone());
     let routes = api.with(warp::log("api"));
-    warp::serve(routes).run(([127, 0, 0, 1], 3030)).await;
+    // Solana clients expect the PubSub endpoint on the port following the RPC one
+    let pubsub = filters::pubsub(context).with(warp::log("pubsub"));
+    tokio::join!(
+        warp::serve(routes).run(([127, 0, 0, 1], 3030)),
+        warp::serve(pubsub).run(([127, 0, 0, 1], 3031)),
+    );
 }
 
 mod filters {
-    use super::{handlers, Context};
-    use warp::Filter;
+    use super::{handlers, pubsub, Context};
+    use warp::{ws::Ws, Filter};
 
     // Routes aggregation
     pub fn api(
         context: Context,
     ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
-        latest_blockhash(context.clone())
+        json_rpc(context.clone())
+            .or(latest_blockhash(context.clone()))
             .or(advance_blockhash(context.clone()))
             .or(set_rpc_config(context.clone()))
+            .or(set_block_production(context.clone()))
+            .or(close_block(context.clone()))
+            .or(set_compute_budget_overrides(context.clone()))
+            .or(register_program_errors(context.clone()))
+            .or(register_anchor_program(context.clone()))
+            .or(register_idl(context.clone()))
             .or(rent_exempt_balance(context.clone()))
             .or(get_account(context.clone()))
             .or(get_accounts(context.clone()))
-            .or(execute_transaction_batch(context))
+            .or(get_program_accounts(context.clone()))
+            .or(get_token_accounts_by_owner(context.clone()))
+            .or(get_token_accounts_by_delegate(context.clone()))
+            .or(get_token_largest_accounts(context.clone()))
+            .or(get_parsed_account(context.clone()))
+            .or(get_parsed_accounts(context.clone()))
+            .or(get_transaction(context.clone()))
+            .or(get_signature_statuses(context.clone()))
+            .or(get_signatures_for_address(context.clone()))
+            .or(get_block(context.clone()))
+            .or(get_blocks(context.clone()))
+            .or(execute_transaction_batch(context.clone()))
+            .or(submit(context.clone()))
+            .or(pending(context.clone()))
+            .or(seal_block(context.clone()))
+            .or(simulate_transaction_batch(context.clone()))
+            .or(simulate_bundle(context.clone()))
+            .or(search_ordering(context.clone()))
+            .or(analyze_schedule(context.clone()))
+            .or(account_graph(context.clone()))
+            .or(estimate_compute_units(context))
+    }
+
+    pub fn pubsub(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path::end()
+            .and(warp::ws())
+            .and(with_context(context))
+            .map(|ws: Ws, context: Context| {
+                ws.on_upgrade(move |socket| pubsub::handle_connection(socket, context))
+            })
     }
 
     // Route definitions
+    pub fn json_rpc(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path::end()
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::json_rpc)
+    }
+
     pub fn latest_blockhash(
         context: Context,
     ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
@@ -74,6 +138,65 @@ mod filters {
             .and_then(handlers::set_rpc_config)
     }
 
+    pub fn set_block_production(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("set_block_production")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::set_block_production)
+    }
+
+    pub fn close_block(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("close_block")
+            .and(warp::post())
+            .and(with_context(context))
+            .and_then(handlers::close_block)
+    }
+
+    pub fn set_compute_budget_overrides(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("set_compute_budget_overrides")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::set_compute_budget_overrides)
+    }
+
+    pub fn register_program_errors(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("register_program_errors")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::register_program_errors)
+    }
+
+    pub fn register_anchor_program(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("register_anchor_program")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::register_anchor_program)
+    }
+
+    pub fn register_idl(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("register_idl")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::register_idl)
+    }
+
     pub fn rent_exempt_balance(
         context: Context,
     ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
@@ -104,16 +227,221 @@ mod filters {
             .and_then(handlers::get_accounts)
     }
 
+    pub fn get_program_accounts(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("get_program_accounts")
+            .and(warp::get())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::get_program_accounts)
+    }
+
+    pub fn get_token_accounts_by_owner(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("get_token_accounts_by_owner")
+            .and(warp::get())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::get_token_accounts_by_owner)
+    }
+
+    pub fn get_token_accounts_by_delegate(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("get_token_accounts_by_delegate")
+            .and(warp::get())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::get_token_accounts_by_delegate)
+    }
+
+    pub fn get_token_largest_accounts(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("get_token_largest_accounts")
+            .and(warp::get())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::get_token_largest_accounts)
+    }
+
+    pub fn get_parsed_account(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("get_parsed_account")
+            .and(warp::get())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::get_parsed_account)
+    }
+
+    pub fn get_parsed_accounts(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("get_parsed_accounts")
+            .and(warp::get())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::get_parsed_accounts)
+    }
+
+    pub fn get_transaction(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("get_transaction")
+            .and(warp::get())
+            .and(warp::query())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::get_transaction)
+    }
+
+    pub fn get_signature_statuses(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("get_signature_statuses")
+            .and(warp::get())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::get_signature_statuses)
+    }
+
+    pub fn get_signatures_for_address(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("get_signatures_for_address")
+            .and(warp::get())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::get_signatures_for_address)
+    }
+
+    pub fn get_block(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("get_block")
+            .and(warp::get())
+            .and(warp::query())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::get_block)
+    }
+
+    pub fn get_blocks(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("get_blocks")
+            .and(warp::get())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::get_blocks)
+    }
+
     pub fn execute_transaction_batch(
         context: Context,
     ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
         warp::path!("execute_transaction_batch")
             .and(warp::post())
+            .and(warp::query())
             .and(warp::body::json())
             .and(with_context(context))
             .and_then(handlers::execute_transaction_batch)
     }
 
+    pub fn submit(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("submit")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::submit)
+    }
+
+    pub fn pending(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("pending")
+            .and(warp::get())
+            .and(with_context(context))
+            .and_then(handlers::pending)
+    }
+
+    pub fn seal_block(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("seal_block")
+            .and(warp::post())
+            .and(warp::query())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::seal_block)
+    }
+
+    pub fn simulate_transaction_batch(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("simulate_transaction_batch")
+            .and(warp::post())
+            .and(warp::query())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::simulate_transaction_batch)
+    }
+
+    pub fn simulate_bundle(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("simulate_bundle")
+            .and(warp::post())
+            .and(warp::query())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::simulate_bundle)
+    }
+
+    pub fn search_ordering(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("search_ordering")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::search_ordering)
+    }
+
+    pub fn analyze_schedule(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("analyze_schedule")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::analyze_schedule)
+    }
+
+    pub fn account_graph(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("account_graph")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::account_graph)
+    }
+
+    pub fn estimate_compute_units(
+        context: Context,
+    ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("estimate_compute_units")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_context(context))
+            .and_then(handlers::estimate_compute_units)
+    }
+
     // Helpers
     fn with_context(
         context: Context,
@@ -123,14 +451,33 @@ mod filters {
 }
 
 mod handlers {
-    use super::Context;
-    use executor_client::RpcConfig;
-    use solana_program::{hash::Hash, pubkey::Pubkey};
-    use solana_sdk::transaction::Transaction;
-    use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+    use super::{pubsub, rpc, Context};
+    use executor_client::{
+        AccountGraphRequest, BlockProduction, BlocksRequest, BundleSimulationRequest,
+        ComputeBudgetOverrides, ComputeUnitsEstimateRequest, EncodedBatchResult, EncodingConfig,
+        GetProgramAccountsRequest, GraphFormat, LargestTokenAccountsRequest, OrderingPolicy,
+        OrderingSearchRequest, RegisterIdlRequest, RegisterProgramErrorsRequest, RpcConfig,
+        ScheduleAnalysisRequest, ScheduleReport, SignaturesForAddressRequest, SimulatedBatch,
+        SimulationRequest, TokenAccountsRequest,
+    };
+    use executor_core::{
+        account_graph::render_dot, blocks::Block, idl::Idl, result::summarize_batch,
+    };
+    use solana_program::{clock::Slot, hash::Hash, pubkey::Pubkey};
+    use solana_sdk::{signature::Signature, transaction::Transaction};
+    use solana_transaction_status::UiTransactionEncoding;
     use std::convert::Infallible;
     use warp::hyper::StatusCode;
 
+    pub async fn json_rpc(
+        request: serde_json::Value,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let mut context = context.lock().await;
+        let response = rpc::process(&mut context, request);
+        Ok(warp::reply::json(&response))
+    }
+
     pub async fn latest_blockhash(context: Context) -> Result<impl warp::Reply, Infallible> {
         let context = context.lock().await;
         let latest_blockhash = context.executor.get_latest_blockhash();
@@ -141,7 +488,7 @@ mod handlers {
         hash: Option<Hash>,
         context: Context,
     ) -> Result<impl warp::Reply, Infallible> {
-        let context = context.lock().await;
+        let mut context = context.lock().await;
         let latest_blockhash = context.executor.advance_blockhash(hash);
         Ok(warp::reply::json(&latest_blockhash))
     }
@@ -157,6 +504,77 @@ mod handlers {
         Ok(StatusCode::OK)
     }
 
+    pub async fn set_block_production(
+        block_production: BlockProduction,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let mut context = context.lock().await;
+        context.executor.set_block_production(block_production);
+        Ok(StatusCode::OK)
+    }
+
+    pub async fn close_block(context: Context) -> Result<impl warp::Reply, Infallible> {
+        let mut context = context.lock().await;
+        let slot = context.executor.close_block();
+        Ok(warp::reply::json(&slot))
+    }
+
+    pub async fn set_compute_budget_overrides(
+        compute_budget_overrides: Option<ComputeBudgetOverrides>,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let mut context = context.lock().await;
+        let result = context
+            .executor
+            .set_compute_budget_overrides(compute_budget_overrides);
+        Ok(match result {
+            Ok(()) => warp::reply::with_status(warp::reply::json(&()), StatusCode::OK),
+            Err(error) => warp::reply::with_status(
+                warp::reply::json(&error.to_string()),
+                StatusCode::BAD_REQUEST,
+            ),
+        })
+    }
+
+    pub async fn register_program_errors(
+        request: RegisterProgramErrorsRequest,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let mut context = context.lock().await;
+        context
+            .executor
+            .register_program_errors(request.program_id, request.errors);
+        Ok(StatusCode::OK)
+    }
+
+    pub async fn register_anchor_program(
+        program_id: Pubkey,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let mut context = context.lock().await;
+        context.executor.register_anchor_program(program_id);
+        Ok(StatusCode::OK)
+    }
+
+    pub async fn register_idl(
+        request: RegisterIdlRequest,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let mut context = context.lock().await;
+        let result = match request.idl {
+            Some(idl) => Idl::from_json(&idl)
+                .map(|idl| context.executor.register_idl(request.program_id, idl)),
+            None => context.executor.fetch_idl(request.program_id),
+        };
+        Ok(match result {
+            Ok(()) => warp::reply::with_status(warp::reply::json(&()), StatusCode::OK),
+            Err(error) => warp::reply::with_status(
+                warp::reply::json(&error.to_string()),
+                StatusCode::BAD_REQUEST,
+            ),
+        })
+    }
+
     pub async fn rent_exempt_balance(
         data_length: usize,
         context: Context,
@@ -186,13 +604,347 @@ mod handlers {
         Ok(warp::reply::json(&maybe_accounts))
     }
 
+    pub async fn get_program_accounts(
+        request: GetProgramAccountsRequest,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let context = context.lock().await;
+        let accounts = context
+            .executor
+            .get_program_accounts(&request.program_id, &request.filters);
+        Ok(warp::reply::json(&accounts))
+    }
+
+    pub async fn get_token_accounts_by_owner(
+        request: TokenAccountsRequest,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let context = context.lock().await;
+        let balances = context
+            .executor
+            .get_token_accounts_by_owner(&request.pubkey, request.mint.as_ref());
+        Ok(match balances {
+            Ok(balances) => warp::reply::with_status(warp::reply::json(&balances), StatusCode::OK),
+            Err(error) => warp::reply::with_status(
+                warp::reply::json(&error.to_string()),
+                StatusCode::BAD_REQUEST,
+            ),
+        })
+    }
+
+    pub async fn get_token_accounts_by_delegate(
+        request: TokenAccountsRequest,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let context = context.lock().await;
+        let balances = context
+            .executor
+            .get_token_accounts_by_delegate(&request.pubkey, request.mint.as_ref());
+        Ok(match balances {
+            Ok(balances) => warp::reply::with_status(warp::reply::json(&balances), StatusCode::OK),
+            Err(error) => warp::reply::with_status(
+                warp::reply::json(&error.to_string()),
+                StatusCode::BAD_REQUEST,
+            ),
+        })
+    }
+
+    pub async fn get_token_largest_accounts(
+        request: LargestTokenAccountsRequest,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let context = context.lock().await;
+        let balances = context
+            .executor
+            .get_token_largest_accounts(&request.mint, request.limit);
+        Ok(match balances {
+            Ok(balances) => warp::reply::with_status(warp::reply::json(&balances), StatusCode::OK),
+            Err(error) => warp::reply::with_status(
+                warp::reply::json(&error.to_string()),
+                StatusCode::BAD_REQUEST,
+            ),
+        })
+    }
+
+    pub async fn get_parsed_account(
+        pubkey: Pubkey,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let context = context.lock().await;
+        let maybe_account = context.executor.get_parsed_account(&pubkey);
+        Ok(warp::reply::json(&maybe_account))
+    }
+
+    pub async fn get_parsed_accounts(
+        pubkeys: Vec<Pubkey>,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let context = context.lock().await;
+        let maybe_accounts = context.executor.get_parsed_accounts(&pubkeys);
+        Ok(warp::reply::json(&maybe_accounts))
+    }
+
+    pub async fn get_transaction(
+        encoding_config: EncodingConfig,
+        signature: Signature,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let context = context.lock().await;
+        let maybe_transaction = context
+            .executor
+            .get_transaction(&signature)
+            .map(|transaction| {
+                transaction
+                    .clone()
+                    .encode(
+                        encoding_config
+                            .encoding
+                            .unwrap_or(UiTransactionEncoding::Binary),
+                        None,
+                    )
+                    .expect("Failed to encode transaction")
+            });
+        Ok(warp::reply::json(&maybe_transaction))
+    }
+
+    pub async fn get_signature_statuses(
+        signatures: Vec<Signature>,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let context = context.lock().await;
+        let statuses = context.executor.get_signature_statuses(&signatures);
+        Ok(warp::reply::json(&statuses))
+    }
+
+    pub async fn get_signatures_for_address(
+        request: SignaturesForAddressRequest,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let context = context.lock().await;
+        let signatures = context.executor.get_signatures_for_address(
+            &request.address,
+            request.before.as_ref(),
+            request.until.as_ref(),
+            request.limit,
+        );
+        Ok(warp::reply::json(&signatures))
+    }
+
+    pub async fn get_block(
+        encoding_config: EncodingConfig,
+        slot: Slot,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let context = context.lock().await;
+        let maybe_block = context.executor.get_block(slot).map(|block| {
+            block
+                .encode(
+                    encoding_config
+                        .encoding
+                        .unwrap_or(UiTransactionEncoding::Binary),
+                    Block::default_encoding_options(),
+                )
+                .expect("Failed to encode block")
+        });
+        Ok(warp::reply::json(&maybe_block))
+    }
+
+    pub async fn get_blocks(
+        request: BlocksRequest,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let context = context.lock().await;
+        let slots = context
+            .executor
+            .get_blocks(request.start_slot, request.end_slot);
+        Ok(warp::reply::json(&slots))
+    }
+
     pub async fn execute_transaction_batch(
+        encoding_config: EncodingConfig,
+        batch: Vec<Transaction>,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let mut context = context.lock().await;
+        let results = context.executor.execute_transaction_batch(&batch);
+        pubsub::publish_results(&context, &results);
+        let batch_result = EncodedBatchResult {
+            results: results
+                .iter()
+                .map(|result| result.encode(&encoding_config))
+                .collect(),
+            summary: summarize_batch(&results),
+        };
+        Ok(warp::reply::json(&batch_result))
+    }
+
+    pub async fn submit(
         batch: Vec<Transaction>,
         context: Context,
     ) -> Result<impl warp::Reply, Infallible> {
         let mut context = context.lock().await;
-        let simulation_results: Vec<EncodedConfirmedTransactionWithStatusMeta> =
-            context.executor.execute_transaction_batch(&batch);
-        Ok(warp::reply::json(&simulation_results))
+        let signatures: anyhow::Result<Vec<Signature>> = batch
+            .into_iter()
+            .map(|tx| context.executor.submit_transaction(tx))
+            .collect();
+        Ok(match signatures {
+            Ok(signatures) => {
+                warp::reply::with_status(warp::reply::json(&signatures), StatusCode::OK)
+            }
+            Err(error) => warp::reply::with_status(
+                warp::reply::json(&error.to_string()),
+                StatusCode::BAD_REQUEST,
+            ),
+        })
+    }
+
+    pub async fn pending(context: Context) -> Result<impl warp::Reply, Infallible> {
+        let context = context.lock().await;
+        let pending_transactions = context.executor.pending_transactions();
+        Ok(warp::reply::json(&pending_transactions))
+    }
+
+    pub async fn seal_block(
+        encoding_config: EncodingConfig,
+        ordering: OrderingPolicy,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let mut context = context.lock().await;
+        Ok(match context.executor.seal_block(&ordering) {
+            Ok(sealed_block) => {
+                pubsub::publish_results(&context, &sealed_block.results);
+                warp::reply::with_status(
+                    warp::reply::json(&sealed_block.encode(&encoding_config)),
+                    StatusCode::OK,
+                )
+            }
+            Err(error) => warp::reply::with_status(
+                warp::reply::json(&error.to_string()),
+                StatusCode::BAD_REQUEST,
+            ),
+        })
+    }
+
+    pub async fn simulate_transaction_batch(
+        encoding_config: EncodingConfig,
+        request: SimulationRequest,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let mut context = context.lock().await;
+        let results = context
+            .executor
+            .simulate_transaction_batch(&request.batch, &request.account_overrides);
+        Ok(match results {
+            Ok(results) => {
+                let simulated_batch = SimulatedBatch {
+                    transactions: results
+                        .iter()
+                        .map(|result| result.encode(&encoding_config))
+                        .collect(),
+                    summary: summarize_batch(results.iter().map(|result| &result.result)),
+                };
+                warp::reply::with_status(warp::reply::json(&simulated_batch), StatusCode::OK)
+            }
+            Err(error) => warp::reply::with_status(
+                warp::reply::json(&error.to_string()),
+                StatusCode::BAD_REQUEST,
+            ),
+        })
+    }
+
+    pub async fn simulate_bundle(
+        encoding_config: EncodingConfig,
+        request: BundleSimulationRequest,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let mut context = context.lock().await;
+        let simulation = context.executor.simulate_bundle(
+            &request.bundle,
+            &request.account_overrides,
+            &request.rules.unwrap_or_default(),
+        );
+        Ok(match simulation {
+            Ok(simulation) => warp::reply::with_status(
+                warp::reply::json(&simulation.encode(&encoding_config)),
+                StatusCode::OK,
+            ),
+            Err(error) => warp::reply::with_status(
+                warp::reply::json(&error.to_string()),
+                StatusCode::BAD_REQUEST,
+            ),
+        })
+    }
+
+    pub async fn search_ordering(
+        request: OrderingSearchRequest,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let mut context = context.lock().await;
+        Ok(match context.executor.search_ordering(&request) {
+            Ok(result) => warp::reply::with_status(warp::reply::json(&result), StatusCode::OK),
+            Err(error) => warp::reply::with_status(
+                warp::reply::json(&error.to_string()),
+                StatusCode::BAD_REQUEST,
+            ),
+        })
+    }
+
+    pub async fn analyze_schedule(
+        request: ScheduleAnalysisRequest,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let mut context = context.lock().await;
+        let report = match request.execute {
+            true => context.executor.simulate_schedule(&request.batch).report(),
+            false => ScheduleReport {
+                analysis: context.executor.analyze_schedule(&request.batch),
+                comparison: None,
+            },
+        };
+        Ok(warp::reply::json(&report))
+    }
+
+    pub async fn account_graph(
+        request: AccountGraphRequest,
+        context: Context,
+    ) -> Result<Box<dyn warp::Reply>, Infallible> {
+        let mut context = context.lock().await;
+        let graph = match context
+            .executor
+            .get_account_graph(&request.batch, &request.account_overrides)
+        {
+            Ok(graph) => graph,
+            Err(error) => {
+                return Ok(Box::new(warp::reply::with_status(
+                    warp::reply::json(&error.to_string()),
+                    StatusCode::BAD_REQUEST,
+                )))
+            }
+        };
+        Ok(match request.format {
+            GraphFormat::Json => Box::new(warp::reply::json(&graph)),
+            GraphFormat::Dot => Box::new(warp::reply::with_header(
+                render_dot(&graph),
+                "content-type",
+                "text/vnd.graphviz",
+            )),
+        })
+    }
+
+    pub async fn estimate_compute_units(
+        request: ComputeUnitsEstimateRequest,
+        context: Context,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let mut context = context.lock().await;
+        let estimate = context
+            .executor
+            .estimate_compute_units(&request.transaction, request.margin_percent);
+        Ok(match estimate {
+            Ok(estimate) => warp::reply::with_status(warp::reply::json(&estimate), StatusCode::OK),
+            Err(error) => warp::reply::with_status(
+                warp::reply::json(&error.to_string()),
+                StatusCode::BAD_REQUEST,
+            ),
+        })
     }
 }
@@ -0,0 +1,362 @@
+//! Solana PubSub compatible WebSocket endpoint, notifying subscribers of the changes made by
+//! executed transactions.
+
+use crate::{
+    rpc::{
+        encode_ui_account, optional_param, param, parse_pubkey, parse_signature, response,
+        split_request, to_value, RpcError, RpcResult,
+    },
+    Context, ContextRaw,
+};
+use executor_core::result::ExecutionResult;
+use futures_util::{SinkExt, StreamExt};
+use itertools::Itertools;
+use serde::Serialize;
+use serde_json::{json, Value};
+use solana_client::{
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcTransactionLogsFilter},
+    rpc_filter::RpcFilterType,
+    rpc_response::{
+        ProcessedSignatureResult, Response, RpcKeyedAccount, RpcLogsResponse, RpcResponseContext,
+        RpcSignatureResult,
+    },
+};
+use solana_sdk::{
+    account::Account, clock::Slot, pubkey::Pubkey, signature::Signature,
+    transaction::TransactionError,
+};
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use tokio::sync::broadcast::error::RecvError;
+use warp::ws::{Message, WebSocket};
+
+/// Capacity of the notification channel, slower subscribers miss older notifications.
+pub const NOTIFICATION_CHANNEL_CAPACITY: usize = 1024;
+
+static NEXT_SUBSCRIPTION_ID: AtomicU64 = AtomicU64::new(0);
+
+/// State change published to the WebSocket connections.
+#[derive(Clone)]
+pub enum Notification {
+    Account {
+        slot: Slot,
+        pubkey: Pubkey,
+        account: Account,
+        spl_token_decimals: Option<u8>,
+    },
+    Transaction {
+        slot: Slot,
+        signature: Signature,
+        err: Option<TransactionError>,
+        logs: Vec<String>,
+        account_keys: Vec<Pubkey>,
+    },
+}
+
+enum Subscription {
+    Account {
+        pubkey: Pubkey,
+        config: RpcAccountInfoConfig,
+    },
+    Program {
+        program_id: Pubkey,
+        config: RpcProgramAccountsConfig,
+    },
+    Logs {
+        filter: RpcTransactionLogsFilter,
+    },
+    Signature {
+        signature: Signature,
+    },
+}
+
+/// Publishes the transactions of a batch, then the final state of the accounts they changed.
+pub fn publish_results(context: &ContextRaw, results: &[ExecutionResult]) {
+    for result in results {
+        // Nothing identifies an unsigned transaction to subscribers, and RPC nodes don't notify
+        // transactions which failed to load
+        let signature = match result.transaction.signatures.first() {
+            Some(signature) if result.executed => *signature,
+            _ => continue,
+        };
+        let _ = context.notifications.send(Notification::Transaction {
+            slot: result.slot,
+            signature,
+            err: result.status.clone().err(),
+            logs: result.log_messages.clone().unwrap_or_default(),
+            account_keys: result.transaction.message.static_account_keys().to_vec(),
+        });
+    }
+
+    let changed_accounts = results
+        .iter()
+        .flat_map(|result| result.account_diffs.iter().map(|diff| diff.pubkey))
+        .unique();
+    for pubkey in changed_accounts {
+        // Closed accounts are notified as empty ones, like RPC nodes do
+        let account = context.executor.get_account(&pubkey).unwrap_or_default();
+        let _ = context.notifications.send(Notification::Account {
+            slot: context.executor.bank().slot(),
+            pubkey,
+            spl_token_decimals: context.executor.get_spl_token_decimals(&account),
+            account,
+        });
+    }
+}
+
+pub async fn handle_connection(socket: WebSocket, context: Context) {
+    let (mut sink, mut stream) = socket.split();
+    let mut notifications = context.lock().await.notifications.subscribe();
+    let mut subscriptions: HashMap<u64, Subscription> = HashMap::new();
+
+    loop {
+        let messages = tokio::select! {
+            message = stream.next() => match message {
+                Some(Ok(message)) if message.is_text() => {
+                    let request = serde_json::from_str(message.to_str().unwrap_or_default())
+                        .unwrap_or(Value::Null);
+                    let context = context.lock().await;
+                    process_request(&context, &mut subscriptions, request)
+                }
+                Some(Ok(message)) if message.is_close() => break,
+                Some(Ok(_)) => continue,
+                Some(Err(_)) | None => break,
+            },
+            notification = notifications.recv() => match notification {
+                Ok(notification) => notify(&mut subscriptions, &notification),
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            },
+        };
+
+        for message in messages {
+            if sink.send(Message::text(message.to_string())).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+fn process_request(
+    context: &ContextRaw,
+    subscriptions: &mut HashMap<u64, Subscription>,
+    request: Value,
+) -> Vec<Value> {
+    let (id, method, params) = split_request(&request);
+    let method = method.unwrap_or_default();
+    let subscription = match method {
+        "accountSubscribe" => parse_account_subscription(&params),
+        "programSubscribe" => parse_program_subscription(&params),
+        "logsSubscribe" => param(&params, 0).map(|filter| Subscription::Logs { filter }),
+        "signatureSubscribe" => param::<String>(&params, 0)
+            .and_then(|signature| parse_signature(&signature))
+            .map(|signature| Subscription::Signature { signature }),
+        "accountUnsubscribe"
+        | "programUnsubscribe"
+        | "logsUnsubscribe"
+        | "signatureUnsubscribe" => {
+            let result = param::<u64>(&params, 0)
+                .and_then(|id| to_value(subscriptions.remove(&id).is_some()));
+            return vec![response(id, result)];
+        }
+        _ => Err(RpcError::method_not_found(method)),
+    };
+    let subscription = match subscription {
+        Ok(subscription) => subscription,
+        Err(error) => return vec![response(id, Err(error))],
+    };
+
+    let subscription_id = NEXT_SUBSCRIPTION_ID.fetch_add(1, Ordering::Relaxed);
+    let mut messages = vec![response(id, to_value(subscription_id))];
+    // Signatures may have been processed before the subscription
+    let processed_status = match &subscription {
+        Subscription::Signature { signature } => context
+            .executor
+            .get_signature_statuses(std::slice::from_ref(signature))
+            .pop()
+            .flatten(),
+        _ => None,
+    };
+    match processed_status {
+        Some(status) => messages.push(signature_notification(
+            subscription_id,
+            status.slot,
+            status.err,
+        )),
+        None => {
+            subscriptions.insert(subscription_id, subscription);
+        }
+    }
+
+    messages
+}
+
+fn parse_account_subscription(params: &[Value]) -> RpcResult<Subscription> {
+    Ok(Subscription::Account {
+        pubkey: parse_pubkey(&param::<String>(params, 0)?)?,
+        config: optional_param(params, 1)?.unwrap_or_default(),
+    })
+}
+
+fn parse_program_subscription(params: &[Value]) -> RpcResult<Subscription> {
+    let config: RpcProgramAccountsConfig = optional_param(params, 1)?.unwrap_or_default();
+    for filter in config.filters.iter().flatten() {
+        match filter {
+            RpcFilterType::DataSize(_) | RpcFilterType::Memcmp(_) => filter
+                .verify()
+                .map_err(|error| RpcError::invalid_params(error.to_string()))?,
+            filter => {
+                return Err(RpcError::invalid_params(format!(
+                    "Unsupported filter: {:?}",
+                    filter
+                )))
+            }
+        }
+    }
+
+    Ok(Subscription::Program {
+        program_id: parse_pubkey(&param::<String>(params, 0)?)?,
+        config,
+    })
+}
+
+fn notify(
+    subscriptions: &mut HashMap<u64, Subscription>,
+    notification: &Notification,
+) -> Vec<Value> {
+    let mut messages = vec![];
+    let mut completed = vec![];
+
+    for (id, subscription) in subscriptions.iter() {
+        match (subscription, notification) {
+            (
+                Subscription::Account { pubkey, config },
+                Notification::Account {
+                    slot,
+                    pubkey: account_pubkey,
+                    account,
+                    spl_token_decimals,
+                },
+            ) if pubkey == account_pubkey => messages.push(notification_message(
+                "accountNotification",
+                *id,
+                *slot,
+                encode_ui_account(pubkey, account, config, *spl_token_decimals),
+            )),
+            (
+                Subscription::Program { program_id, config },
+                Notification::Account {
+                    slot,
+                    pubkey,
+                    account,
+                    spl_token_decimals,
+                },
+            ) if &account.owner == program_id
+                && config
+                    .filters
+                    .iter()
+                    .flatten()
+                    .all(|filter| filter_matches(filter, &account.data)) =>
+            {
+                messages.push(notification_message(
+                    "programNotification",
+                    *id,
+                    *slot,
+                    RpcKeyedAccount {
+                        pubkey: pubkey.to_string(),
+                        account: encode_ui_account(
+                            pubkey,
+                            account,
+                            &config.account_config,
+                            *spl_token_decimals,
+                        ),
+                    },
+                ))
+            }
+            (
+                Subscription::Logs { filter },
+                Notification::Transaction {
+                    slot,
+                    signature,
+                    err,
+                    logs,
+                    account_keys,
+                },
+            ) if logs_filter_matches(filter, account_keys) => messages.push(notification_message(
+                "logsNotification",
+                *id,
+                *slot,
+                RpcLogsResponse {
+                    signature: signature.to_string(),
+                    err: err.clone(),
+                    logs: logs.clone(),
+                },
+            )),
+            (
+                Subscription::Signature { signature },
+                Notification::Transaction {
+                    slot,
+                    signature: transaction_signature,
+                    err,
+                    ..
+                },
+            ) if signature == transaction_signature => {
+                messages.push(signature_notification(*id, *slot, err.clone()));
+                completed.push(*id);
+            }
+            _ => {}
+        }
+    }
+
+    // Signature subscriptions end with their notification
+    for id in completed {
+        subscriptions.remove(&id);
+    }
+
+    messages
+}
+
+fn filter_matches(filter: &RpcFilterType, data: &[u8]) -> bool {
+    match filter {
+        RpcFilterType::DataSize(size) => data.len() as u64 == *size,
+        RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(data),
+        // Rejected when subscribing
+        RpcFilterType::TokenAccountState => false,
+    }
+}
+
+fn logs_filter_matches(filter: &RpcTransactionLogsFilter, account_keys: &[Pubkey]) -> bool {
+    match filter {
+        RpcTransactionLogsFilter::All | RpcTransactionLogsFilter::AllWithVotes => true,
+        RpcTransactionLogsFilter::Mentions(mentions) => mentions.iter().any(|mention| {
+            account_keys
+                .iter()
+                .any(|account_key| account_key.to_string() == *mention)
+        }),
+    }
+}
+
+fn signature_notification(id: u64, slot: Slot, err: Option<TransactionError>) -> Value {
+    notification_message(
+        "signatureNotification",
+        id,
+        slot,
+        RpcSignatureResult::ProcessedSignature(ProcessedSignatureResult { err }),
+    )
+}
+
+fn notification_message<T: Serialize>(method: &str, id: u64, slot: Slot, value: T) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": method,
+        "params": {
+            "result": Response {
+                context: RpcResponseContext::new(slot),
+                value,
+            },
+            "subscription": id,
+        },
+    })
+}
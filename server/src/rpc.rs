@@ -0,0 +1,492 @@
+//! Solana JSON-RPC 2.0 compatible endpoint, implementing the subset of methods common tools
+//! rely on.
+
+use crate::{pubsub, ContextRaw};
+use executor_core::executor::Executor;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use solana_account_decoder::{
+    parse_account_data::AccountAdditionalData, UiAccount, UiAccountEncoding,
+};
+use solana_client::{
+    rpc_config::{
+        RpcAccountInfoConfig, RpcBlockConfig, RpcSendTransactionConfig,
+        RpcSignaturesForAddressConfig, RpcSimulateTransactionConfig, RpcTransactionConfig,
+    },
+    rpc_request::MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS2_LIMIT,
+    rpc_response::{
+        Response, RpcBlockhash, RpcConfirmedTransactionStatusWithSignature, RpcResponseContext,
+        RpcSimulateTransactionResult,
+    },
+};
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    clock::Slot,
+    pubkey::Pubkey,
+    sanitize::Sanitize,
+    signature::Signature,
+    transaction::{Transaction, VersionedTransaction},
+};
+use solana_transaction_status::{
+    BlockEncodingOptions, TransactionConfirmationStatus, UiTransactionEncoding,
+    UiTransactionReturnData,
+};
+use std::str::FromStr;
+
+const JSON_RPC_VERSION: &str = "2.0";
+
+// JSON-RPC 2.0 and Solana error codes
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SEND_TRANSACTION_PREFLIGHT_FAILURE: i64 = -32002;
+const SLOT_SKIPPED: i64 = -32007;
+
+pub(crate) struct RpcError {
+    code: i64,
+    message: String,
+    data: Option<Value>,
+}
+
+impl RpcError {
+    pub(crate) fn invalid_params(message: impl Into<String>) -> Self {
+        Self {
+            code: INVALID_PARAMS,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    pub(crate) fn method_not_found(method: &str) -> Self {
+        Self {
+            code: METHOD_NOT_FOUND,
+            message: format!("Method not found: {}", method),
+            data: None,
+        }
+    }
+
+    fn slot_skipped(slot: Slot) -> Self {
+        Self {
+            code: SLOT_SKIPPED,
+            message: format!(
+                "Slot {} was skipped, or missing due to ledger jump to recent snapshot",
+                slot
+            ),
+            data: None,
+        }
+    }
+}
+
+pub(crate) type RpcResult<T> = Result<T, RpcError>;
+
+/// Processes a single JSON-RPC request or a batch of them.
+pub fn process(context: &mut ContextRaw, request: Value) -> Value {
+    match request {
+        Value::Array(requests) => Value::Array(
+            requests
+                .into_iter()
+                .map(|request| process_request(context, request))
+                .collect(),
+        ),
+        request => process_request(context, request),
+    }
+}
+
+fn process_request(context: &mut ContextRaw, request: Value) -> Value {
+    let (id, method, params) = split_request(&request);
+    let result = match method {
+        Some(method) => dispatch(context, method, &params),
+        None => Err(RpcError {
+            code: INVALID_REQUEST,
+            message: "Invalid request".to_string(),
+            data: None,
+        }),
+    };
+    response(id, result)
+}
+
+/// Id, method and params of a request.
+pub(crate) fn split_request(request: &Value) -> (Value, Option<&str>, Vec<Value>) {
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let method = request.get("method").and_then(Value::as_str);
+    let params = match request.get("params") {
+        Some(Value::Array(params)) => params.clone(),
+        _ => vec![],
+    };
+    (id, method, params)
+}
+
+pub(crate) fn response(id: Value, result: RpcResult<Value>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": JSON_RPC_VERSION, "result": result, "id": id }),
+        Err(error) => {
+            let mut error_object = json!({ "code": error.code, "message": error.message });
+            if let Some(data) = error.data {
+                error_object["data"] = data;
+            }
+            json!({ "jsonrpc": JSON_RPC_VERSION, "error": error_object, "id": id })
+        }
+    }
+}
+
+fn dispatch(context: &mut ContextRaw, method: &str, params: &[Value]) -> RpcResult<Value> {
+    match method {
+        "getAccountInfo" => get_account_info(&context.executor, params),
+        "getMultipleAccounts" => get_multiple_accounts(&context.executor, params),
+        "getBalance" => get_balance(&context.executor, params),
+        "getLatestBlockhash" => get_latest_blockhash(&context.executor),
+        "sendTransaction" => send_transaction(context, params),
+        "simulateTransaction" => simulate_transaction(&mut context.executor, params),
+        "getSignatureStatuses" => get_signature_statuses(&context.executor, params),
+        "getSignaturesForAddress" => get_signatures_for_address(&context.executor, params),
+        "getTransaction" => get_transaction(&context.executor, params),
+        "getMinimumBalanceForRentExemption" => {
+            let data_len: usize = param(params, 0)?;
+            to_value(context.executor.get_minimum_rent_exempt_balance(data_len))
+        }
+        "getSlot" => to_value(context.executor.bank().slot()),
+        "getBlockHeight" => to_value(context.executor.bank().block_height()),
+        "getBlock" => get_block(&context.executor, params),
+        "getBlocks" => {
+            let start_slot: Slot = param(params, 0)?;
+            let end_slot: Option<Slot> = optional_param(params, 1)?;
+            to_value(context.executor.get_blocks(start_slot, end_slot))
+        }
+        "getBlocksWithLimit" => {
+            let start_slot: Slot = param(params, 0)?;
+            let limit: usize = param(params, 1)?;
+            let mut slots = context.executor.get_blocks(start_slot, None);
+            slots.truncate(limit);
+            to_value(slots)
+        }
+        "getBlockTime" => {
+            let slot: Slot = param(params, 0)?;
+            let block = context
+                .executor
+                .get_block(slot)
+                .ok_or_else(|| RpcError::slot_skipped(slot))?;
+            to_value(block.block.block_time)
+        }
+        _ => Err(RpcError::method_not_found(method)),
+    }
+}
+
+fn get_account_info(executor: &Executor, params: &[Value]) -> RpcResult<Value> {
+    let pubkey = parse_pubkey(&param::<String>(params, 0)?)?;
+    let config: RpcAccountInfoConfig = optional_param(params, 1)?.unwrap_or_default();
+
+    let account = executor
+        .get_account(&pubkey)
+        .map(|account| encode_account(executor, &pubkey, &account, &config));
+    with_context(executor, account)
+}
+
+fn get_multiple_accounts(executor: &Executor, params: &[Value]) -> RpcResult<Value> {
+    let pubkeys = param::<Vec<String>>(params, 0)?
+        .iter()
+        .map(|pubkey| parse_pubkey(pubkey))
+        .collect::<RpcResult<Vec<_>>>()?;
+    let config: RpcAccountInfoConfig = optional_param(params, 1)?.unwrap_or_default();
+
+    let accounts: Vec<Option<UiAccount>> = pubkeys
+        .iter()
+        .map(|pubkey| {
+            executor
+                .get_account(pubkey)
+                .map(|account| encode_account(executor, pubkey, &account, &config))
+        })
+        .collect();
+    with_context(executor, accounts)
+}
+
+fn get_balance(executor: &Executor, params: &[Value]) -> RpcResult<Value> {
+    let pubkey = parse_pubkey(&param::<String>(params, 0)?)?;
+    let balance = executor
+        .get_account(&pubkey)
+        .map_or(0, |account| account.lamports);
+    with_context(executor, balance)
+}
+
+fn get_latest_blockhash(executor: &Executor) -> RpcResult<Value> {
+    let blockhash = executor.get_latest_blockhash();
+    let last_valid_block_height = executor
+        .bank()
+        .get_blockhash_last_valid_block_height(&blockhash)
+        .expect("Latest blockhash must be valid");
+    with_context(
+        executor,
+        RpcBlockhash {
+            blockhash: blockhash.to_string(),
+            last_valid_block_height,
+        },
+    )
+}
+
+fn send_transaction(context: &mut ContextRaw, params: &[Value]) -> RpcResult<Value> {
+    let config: RpcSendTransactionConfig = optional_param(params, 1)?.unwrap_or_default();
+    let transaction = decode_transaction(
+        &param::<String>(params, 0)?,
+        config.encoding.unwrap_or(UiTransactionEncoding::Base58),
+    )?;
+
+    if !config.skip_preflight {
+        let simulation = simulate(&mut context.executor, &transaction, None)?;
+        if simulation.err.is_some() {
+            return Err(RpcError {
+                code: SEND_TRANSACTION_PREFLIGHT_FAILURE,
+                message: format!(
+                    "Transaction simulation failed: {}",
+                    simulation.err.as_ref().unwrap()
+                ),
+                data: Some(to_value(simulation)?),
+            });
+        }
+    }
+
+    let signature = *transaction
+        .signatures
+        .first()
+        .ok_or_else(|| RpcError::invalid_params("Transaction is not signed"))?;
+    let results = context
+        .executor
+        .execute_transaction_batch(std::slice::from_ref(&transaction));
+    pubsub::publish_results(context, &results);
+
+    to_value(signature.to_string())
+}
+
+fn simulate_transaction(executor: &mut Executor, params: &[Value]) -> RpcResult<Value> {
+    let config: RpcSimulateTransactionConfig = optional_param(params, 1)?.unwrap_or_default();
+    let mut transaction = decode_transaction(
+        &param::<String>(params, 0)?,
+        config.encoding.unwrap_or(UiTransactionEncoding::Base58),
+    )?;
+    if config.replace_recent_blockhash {
+        transaction.message.recent_blockhash = executor.get_latest_blockhash();
+    }
+
+    let simulation = simulate(executor, &transaction, Some(&config))?;
+    with_context(executor, simulation)
+}
+
+fn simulate(
+    executor: &mut Executor,
+    transaction: &Transaction,
+    config: Option<&RpcSimulateTransactionConfig>,
+) -> RpcResult<RpcSimulateTransactionResult> {
+    let requested_accounts = match config.and_then(|config| config.accounts.as_ref()) {
+        Some(accounts_config) => Some((
+            accounts_config
+                .addresses
+                .iter()
+                .map(|pubkey| parse_pubkey(pubkey))
+                .collect::<RpcResult<Vec<_>>>()?,
+            RpcAccountInfoConfig {
+                encoding: accounts_config.encoding,
+                ..RpcAccountInfoConfig::default()
+            },
+        )),
+        None => None,
+    };
+
+    let simulation = executor
+        .simulate_transaction_batch(std::slice::from_ref(transaction), &[])
+        .map_err(|error| RpcError::invalid_params(error.to_string()))?
+        .pop()
+        .expect("Batch of one transaction must yield a result");
+    let accounts = requested_accounts.map(|(pubkeys, account_config)| {
+        pubkeys
+            .iter()
+            .map(|pubkey| {
+                simulation
+                    .post_accounts
+                    .iter()
+                    .find(|(address, _)| address == pubkey)
+                    .and_then(|(_, account)| account.clone())
+                    .or_else(|| executor.get_account(pubkey))
+                    .map(|account| encode_account(executor, pubkey, &account, &account_config))
+            })
+            .collect()
+    });
+
+    let result = simulation.result;
+    Ok(RpcSimulateTransactionResult {
+        err: result.status.err(),
+        logs: result.log_messages,
+        accounts,
+        units_consumed: Some(result.compute_units_consumed),
+        return_data: result.return_data.map(UiTransactionReturnData::from),
+    })
+}
+
+fn get_signature_statuses(executor: &Executor, params: &[Value]) -> RpcResult<Value> {
+    let signatures = param::<Vec<String>>(params, 0)?
+        .iter()
+        .map(|signature| parse_signature(signature))
+        .collect::<RpcResult<Vec<_>>>()?;
+    with_context(executor, executor.get_signature_statuses(&signatures))
+}
+
+fn get_signatures_for_address(executor: &Executor, params: &[Value]) -> RpcResult<Value> {
+    let address = parse_pubkey(&param::<String>(params, 0)?)?;
+    let config: RpcSignaturesForAddressConfig = optional_param(params, 1)?.unwrap_or_default();
+    let before = config.before.as_deref().map(parse_signature).transpose()?;
+    let until = config.until.as_deref().map(parse_signature).transpose()?;
+    let limit = config
+        .limit
+        .unwrap_or(MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS2_LIMIT)
+        .min(MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS2_LIMIT);
+
+    let signatures: Vec<RpcConfirmedTransactionStatusWithSignature> = executor
+        .get_signatures_for_address(&address, before.as_ref(), until.as_ref(), limit)
+        .into_iter()
+        .map(|info| RpcConfirmedTransactionStatusWithSignature {
+            signature: info.signature.to_string(),
+            slot: info.slot,
+            err: info.err,
+            memo: info.memo,
+            block_time: info.block_time,
+            confirmation_status: Some(TransactionConfirmationStatus::Finalized),
+        })
+        .collect();
+    to_value(signatures)
+}
+
+fn get_transaction(executor: &Executor, params: &[Value]) -> RpcResult<Value> {
+    let signature = parse_signature(&param::<String>(params, 0)?)?;
+    let config: RpcTransactionConfig = optional_param(params, 1)?.unwrap_or_default();
+
+    match executor.get_transaction(&signature) {
+        Some(transaction) => to_value(
+            transaction
+                .clone()
+                .encode(
+                    config.encoding.unwrap_or(UiTransactionEncoding::Json),
+                    config.max_supported_transaction_version,
+                )
+                .map_err(|error| RpcError::invalid_params(error.to_string()))?,
+        ),
+        None => Ok(Value::Null),
+    }
+}
+
+fn get_block(executor: &Executor, params: &[Value]) -> RpcResult<Value> {
+    let slot: Slot = param(params, 0)?;
+    let config: RpcBlockConfig = optional_param(params, 1)?.unwrap_or_default();
+    let block = executor
+        .get_block(slot)
+        .ok_or_else(|| RpcError::slot_skipped(slot))?;
+
+    to_value(
+        block
+            .block
+            .clone()
+            .encode_with_options(
+                config.encoding.unwrap_or(UiTransactionEncoding::Json),
+                BlockEncodingOptions {
+                    transaction_details: config.transaction_details.unwrap_or_default(),
+                    show_rewards: config.rewards.unwrap_or(true),
+                    max_supported_transaction_version: config.max_supported_transaction_version,
+                },
+            )
+            .map_err(|error| RpcError::invalid_params(error.to_string()))?,
+    )
+}
+
+fn encode_account(
+    executor: &Executor,
+    pubkey: &Pubkey,
+    account: &Account,
+    config: &RpcAccountInfoConfig,
+) -> UiAccount {
+    encode_ui_account(
+        pubkey,
+        account,
+        config,
+        executor.get_spl_token_decimals(account),
+    )
+}
+
+pub(crate) fn encode_ui_account(
+    pubkey: &Pubkey,
+    account: &Account,
+    config: &RpcAccountInfoConfig,
+    spl_token_decimals: Option<u8>,
+) -> UiAccount {
+    let encoding = config.encoding.unwrap_or(UiAccountEncoding::Binary);
+    let additional_data = match encoding {
+        UiAccountEncoding::JsonParsed => Some(AccountAdditionalData { spl_token_decimals }),
+        _ => None,
+    };
+    UiAccount::encode(
+        pubkey,
+        &AccountSharedData::from(account.clone()),
+        encoding,
+        additional_data,
+        config.data_slice,
+    )
+}
+
+fn decode_transaction(encoded: &str, encoding: UiTransactionEncoding) -> RpcResult<Transaction> {
+    let bytes = match encoding {
+        UiTransactionEncoding::Base58 => bs58::decode(encoded)
+            .into_vec()
+            .map_err(|error| RpcError::invalid_params(format!("Invalid base58: {}", error)))?,
+        UiTransactionEncoding::Base64 => base64::decode(encoded)
+            .map_err(|error| RpcError::invalid_params(format!("Invalid base64: {}", error)))?,
+        encoding => {
+            return Err(RpcError::invalid_params(format!(
+                "Unsupported transaction encoding: {:?}",
+                encoding
+            )))
+        }
+    };
+    let transaction = bincode::deserialize::<VersionedTransaction>(&bytes)
+        .map_err(|error| RpcError::invalid_params(format!("Invalid transaction: {}", error)))?
+        .into_legacy_transaction()
+        .ok_or_else(|| RpcError::invalid_params("Versioned transactions are not supported"))?;
+    // Rejects, among others, transactions without the fee payer's signature
+    transaction
+        .sanitize()
+        .map_err(|error| RpcError::invalid_params(format!("Invalid transaction: {}", error)))?;
+    Ok(transaction)
+}
+
+fn with_context<T: Serialize>(executor: &Executor, value: T) -> RpcResult<Value> {
+    to_value(Response {
+        context: RpcResponseContext::new(executor.bank().slot()),
+        value,
+    })
+}
+
+pub(crate) fn to_value<T: Serialize>(value: T) -> RpcResult<Value> {
+    Ok(serde_json::to_value(value).expect("RPC responses must serialize"))
+}
+
+pub(crate) fn param<T: DeserializeOwned>(params: &[Value], index: usize) -> RpcResult<T> {
+    optional_param(params, index)?
+        .ok_or_else(|| RpcError::invalid_params(format!("Missing parameter {}", index)))
+}
+
+pub(crate) fn optional_param<T: DeserializeOwned>(
+    params: &[Value],
+    index: usize,
+) -> RpcResult<Option<T>> {
+    match params.get(index) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => serde_json::from_value(value.clone())
+            .map(Some)
+            .map_err(|error| {
+                RpcError::invalid_params(format!("Invalid parameter {}: {}", index, error))
+            }),
+    }
+}
+
+pub(crate) fn parse_pubkey(pubkey: &str) -> RpcResult<Pubkey> {
+    Pubkey::from_str(pubkey).map_err(|_| RpcError::invalid_params("Invalid param: Invalid pubkey"))
+}
+
+pub(crate) fn parse_signature(signature: &str) -> RpcResult<Signature> {
+    Signature::from_str(signature)
+        .map_err(|_| RpcError::invalid_params("Invalid param: Invalid signature"))
+}